@@ -0,0 +1,47 @@
+//! [`proptest`] strategies producing uniquely-solvable puzzles
+
+use proptest::{
+    arbitrary::Arbitrary,
+    prelude::any,
+    strategy::{BoxedStrategy, Strategy},
+};
+use rand::{SeedableRng, rngs::StdRng};
+
+use crate::Sudoku;
+
+/// Generates puzzles with exactly one solution. Each puzzle is derived from a
+/// random seed, so failing cases can be reproduced from the seed alone
+pub fn puzzles() -> impl Strategy<Value = Sudoku> {
+    any::<u64>()
+        .prop_map(|seed| Sudoku::generate(&mut StdRng::seed_from_u64(seed)))
+}
+
+impl Arbitrary for Sudoku {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        puzzles().boxed()
+    }
+}
+
+#[cfg(all(test, feature = "proptest"))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::puzzles;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(8))]
+
+        #[test]
+        fn solution_keeps_every_given(puzzle in puzzles()) {
+            let mut solved = puzzle;
+            solved.initialize_superpositions();
+            prop_assert!(solved.solve().is_ok());
+
+            prop_assert!(solved.is_complete_and_correct());
+            prop_assert!(puzzle.is_subset_of(&solved));
+        }
+    }
+}
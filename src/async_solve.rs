@@ -0,0 +1,40 @@
+use crate::{SolveError, Sudoku};
+
+impl Sudoku {
+    /// Solves the board like [`Sudoku::solve_into`] on tokio's blocking
+    /// thread pool, so a long solve doesn't hold up other tasks on the
+    /// runtime. Must be awaited from within a tokio runtime
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Sudoku::solve`] would
+    ///
+    /// # Panics
+    ///
+    /// Panics if the solve itself panics, or if called outside a tokio
+    /// runtime
+    pub async fn solve_async(self) -> Result<Self, SolveError> {
+        tokio::task::spawn_blocking(move || self.solve_into())
+            .await
+            .expect("Solver task panicked")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Sudoku;
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    #[test]
+    fn solves_on_a_runtime() {
+        let puzzle: Sudoku = HARD.parse().unwrap();
+
+        let runtime =
+            tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let solution = runtime.block_on(puzzle.solve_async()).unwrap();
+
+        assert!(solution.is_complete_and_correct());
+        assert_eq!(solution.grid, puzzle.solve_into().unwrap().grid);
+    }
+}
@@ -0,0 +1,432 @@
+use crate::{BOARD_LEN, BOARD_SIZE, Cell, Sudoku};
+
+impl Cell {
+    /// A superposition of the values set in `mask`, the inverse of
+    /// [`Cell::candidate_mask`]
+    pub(crate) fn from_candidate_mask(mask: u16) -> Self {
+        Self::Superposition(std::array::from_fn(|idx| mask & (1 << idx) != 0))
+    }
+
+    /// The possible values of the cell as a bitmask, where bit `n - 1` is set
+    /// if `n` is possible. `Empty` cells haven't been constrained yet, so every
+    /// value is possible
+    pub(crate) const fn candidate_mask(&self) -> u16 {
+        match self {
+            Self::Empty => (1 << BOARD_LEN) - 1,
+            Self::Fixed(n) | Self::Collapsed(n) => 1 << (*n - 1),
+            Self::Superposition(s) => {
+                let mut mask = 0;
+                let mut idx = 0;
+
+                while idx < BOARD_LEN {
+                    if s[idx] {
+                        mask |= 1 << idx;
+                    }
+                    idx += 1;
+                }
+
+                mask
+            }
+        }
+    }
+}
+
+impl Sudoku {
+    /// The candidates of every cell as bitmasks indexed by `[row][col]`. Bit
+    /// `n - 1` of a mask is set if `n` is still possible in that cell
+    #[must_use]
+    pub fn candidate_grid(&self) -> [[u16; BOARD_LEN]; BOARD_LEN] {
+        let mut grid = [[0; BOARD_LEN]; BOARD_LEN];
+
+        for (idx, cell) in self.grid.iter().enumerate() {
+            let (row, col) = Self::idx_to_coord(idx);
+            grid[row][col] = cell.candidate_mask();
+        }
+
+        grid
+    }
+
+    /// Marks every cell, indexed by `[row][col]`, where `value` is still a
+    /// candidate or has already been placed. As with
+    /// [`Sudoku::candidate_grid`], `Empty` cells allow every value
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` isn't in `1..=9`
+    #[must_use]
+    pub fn candidates_for_value(
+        &self,
+        value: u8,
+    ) -> [[bool; BOARD_LEN]; BOARD_LEN] {
+        assert!(
+            (1..=BOARD_LEN).contains(&usize::from(value)),
+            "Value out of range"
+        );
+
+        let bit = 1 << (value - 1);
+
+        self.candidate_grid().map(|row| row.map(|mask| mask & bit != 0))
+    }
+
+    /// The flat indices of every cell with a superposition, most constrained
+    /// first. Cells with the same number of candidates stay in board order.
+    /// This is the order a minimum-remaining-values search would guess in
+    #[must_use]
+    pub fn hardest_cell_order(&self) -> Vec<usize> {
+        let mut order: Vec<(usize, usize)> = self
+            .grid
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, cell)| {
+                cell.count_superstates().map(|count| (count, idx))
+            })
+            .collect();
+
+        order.sort_unstable();
+        order.into_iter().map(|(_, idx)| idx).collect()
+    }
+
+    /// The total number of candidates left across every cell with a
+    /// superposition. Each productive deterministic pass lowers it, and it
+    /// reaches 0 once the board is solved
+    #[must_use]
+    pub fn candidate_count_total(&self) -> usize {
+        self.grid.iter().filter_map(Cell::count_superstates).sum()
+    }
+
+    /// The uncertainty left on the board in bits: the sum of `log2` of the
+    /// candidate count of every cell with a superposition. It is 0 once every
+    /// such cell is down to one candidate and never grows as candidates are
+    /// removed. Cells with no candidates at all are skipped
+    #[must_use]
+    pub fn entropy(&self) -> f64 {
+        self.grid
+            .iter()
+            .filter(|cell| matches!(cell, Cell::Superposition(_)))
+            .map(|cell| cell.candidate_mask().count_ones())
+            .filter(|&count| count > 0)
+            .fold(0.0, |bits, count| bits + f64::from(count).log2())
+    }
+
+    /// Removes `value` from the candidates of every cell not listed in
+    /// `allowed_cells`, so it can only be placed in those cells. Only cells
+    /// that already have a superposition are changed, so call this after
+    /// [`Sudoku::initialize_superpositions`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` isn't in `1..=9` or a coordinate is off the board
+    pub fn restrict_digit(
+        &mut self,
+        value: u8,
+        allowed_cells: &[(usize, usize)],
+    ) {
+        assert!(
+            (1..=BOARD_LEN).contains(&usize::from(value)),
+            "Value out of range"
+        );
+
+        let mut allowed = [false; BOARD_SIZE];
+
+        for &cell in allowed_cells {
+            allowed[Self::checked_coord_to_idx(cell)] = true;
+        }
+
+        for idx in (0..BOARD_SIZE).filter(|&idx| !allowed[idx]) {
+            self.eliminate(idx, value);
+        }
+    }
+
+    /// Every unfilled cell with exactly one candidate left, as
+    /// `(row, col, value)`. The board isn't changed
+    #[must_use]
+    pub fn naked_singles(&self) -> Vec<(usize, usize, u8)> {
+        self.grid
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, cell)| {
+                let (row, col) = Self::idx_to_coord(idx);
+                cell.collapse().map(|value| (row, col, value))
+            })
+            .collect()
+    }
+
+    /// Fills in the cells that are forced right now, by a naked or hidden
+    /// single on the board as it stands, and returns how many were placed.
+    /// Every forced cell is found before any is placed, so cells that only
+    /// become forced once the others are filled are left for the next call.
+    /// Candidates are initialized first if they haven't been
+    pub fn fill_obvious(&mut self) -> usize {
+        self.initialize_superpositions();
+        self.propagate_all();
+
+        let forced: Vec<_> = (0..self.grid.len())
+            .filter_map(|idx| Some((idx, self.forced_value(idx)?)))
+            .collect();
+
+        let mut filled = 0;
+
+        for (idx, value) in forced {
+            // A contradictory board can force the same value on two peers
+            if self
+                .all_peers(idx)
+                .any(|peer| self.grid[peer].value() == Some(value))
+            {
+                continue;
+            }
+
+            self.grid[idx] = Cell::Collapsed(value);
+            self.propagate(idx);
+            filled += 1;
+        }
+
+        filled
+    }
+
+    /// The value the cell at `idx` is forced to hold by a naked or hidden
+    /// single, without placing it
+    fn forced_value(&self, idx: usize) -> Option<u8> {
+        if let Some(value) = self.grid[idx].collapse() {
+            return Some(value);
+        }
+
+        let mut probe = *self;
+
+        if probe.solve_pure_negative(idx) {
+            probe.grid[idx].value()
+        } else {
+            None
+        }
+    }
+
+    /// Whether no unfilled cell lists a candidate already held by one of its
+    /// peers, as should hold for any candidates the solver produced. Stale or
+    /// corrupted pencil marks, e.g. from a hand-edited save string, fail this
+    #[must_use]
+    pub fn is_pencilmark_consistent(&self) -> bool {
+        (0..self.grid.len()).all(|idx| {
+            let Cell::Superposition(_) = self.grid[idx] else {
+                return true;
+            };
+
+            let taken = self
+                .all_peers(idx)
+                .filter_map(|peer| self.grid[peer].value())
+                .fold(0, |mask, n| mask | 1 << (n - 1));
+
+            self.grid[idx].candidate_mask() & taken == 0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Sudoku};
+
+    // Three cells are forced at the start, and filling them forces three more
+    const PUZZLE: &str = "..23.7....1.4.....8.3..56...3....96..9.5............4.6.78...2....23...9......1..";
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    const HARD_SOLUTION: &str = "126437958895621473374985126457193862983246517612578394269314785548769231731852649";
+
+    fn initialized(puzzle: &str) -> Sudoku {
+        let mut board: Sudoku = puzzle.parse().unwrap();
+        board.initialize_superpositions();
+        board
+    }
+
+    #[test]
+    fn fill_obvious_places_only_cells_forced_at_the_start() {
+        let mut solution: Sudoku = PUZZLE.parse().unwrap();
+        solution.initialize_superpositions();
+        solution.solve().unwrap();
+
+        let mut board: Sudoku = PUZZLE.parse().unwrap();
+        assert_eq!(board.fill_obvious(), 3);
+        assert_eq!(board.clue_count() + 3, 81 - board.empty_cells().len());
+        assert_eq!(board.fill_obvious(), 3);
+
+        assert!(board.grid.iter().zip(solution.grid).all(|(cell, solved)| {
+            cell.value().is_none() || cell.value() == solved.value()
+        }));
+    }
+
+    #[test]
+    fn naked_singles_lists_cells_with_one_candidate() {
+        let mut board: Sudoku = HARD_SOLUTION.parse().unwrap();
+        for cell in [(0, 0), (4, 4), (8, 8)] {
+            board.set(cell.0, cell.1, 0);
+        }
+        board.initialize_superpositions();
+
+        // Nothing has been propagated, so every blank could still be anything
+        assert!(board.naked_singles().is_empty());
+
+        board.propagate_all();
+        let before = board.grid;
+
+        assert_eq!(board.naked_singles(), [(0, 0, 1), (4, 4, 4), (8, 8, 9)]);
+        assert_eq!(board.grid, before);
+    }
+
+    #[test]
+    fn candidate_grid_of_an_empty_board_allows_everything() {
+        let all_open = |board: &Sudoku| {
+            board.candidate_grid().iter().flatten().all(|&m| m == 0b1_1111_1111)
+        };
+
+        let mut board = Sudoku::empty();
+        assert!(all_open(&board));
+
+        board.initialize_superpositions();
+        assert!(all_open(&board));
+
+        // Filled cells have only their own value, at `[row][col]`
+        let grid = initialized(HARD).candidate_grid();
+        assert_eq!(grid[0][1], 1 << 1);
+        assert_eq!(grid[1][8], 1 << 2);
+    }
+
+    #[test]
+    fn candidates_for_value_excludes_the_peers_of_a_placement() {
+        let mut board = Sudoku::empty().with_clue(4, 4, 7);
+        board.initialize_superpositions();
+        board.propagate_all();
+
+        let sevens = board.candidates_for_value(7);
+
+        for (row, cols) in sevens.iter().enumerate() {
+            for (col, &possible) in cols.iter().enumerate() {
+                let peer =
+                    (row == 4 || col == 4 || (row / 3 == 1 && col / 3 == 1))
+                        && (row, col) != (4, 4);
+
+                assert_eq!(possible, !peer, "({row}, {col})");
+            }
+        }
+
+        // Other values are unaffected except in the placed cell
+        let eights = board.candidates_for_value(8);
+        assert_eq!(eights.iter().flatten().filter(|&&b| !b).count(), 1);
+        assert!(!eights[4][4]);
+    }
+
+    #[test]
+    fn hardest_cells_come_first() {
+        let mut board = initialized(HARD);
+        board.propagate_all();
+
+        let order = board.hardest_cell_order();
+        let counts: Vec<usize> = order
+            .iter()
+            .map(|&idx| board.grid[idx].count_superstates().unwrap())
+            .collect();
+
+        assert_eq!(order.len(), 81 - board.clue_count());
+        assert!(matches!(board.grid[order[0]], Cell::Superposition(_)));
+        assert_eq!(
+            counts[0],
+            board
+                .grid
+                .iter()
+                .filter_map(Cell::count_superstates)
+                .min()
+                .unwrap()
+        );
+        assert!(counts.is_sorted());
+
+        // Ties stay in board order
+        assert!(
+            order
+                .windows(2)
+                .zip(counts.windows(2))
+                .all(|(idx, count)| count[0] < count[1] || idx[0] < idx[1])
+        );
+    }
+
+    #[test]
+    fn pencil_marks_naming_a_peer_value_are_inconsistent() {
+        let mut board = initialized(HARD);
+
+        // Freshly initialized candidates ignore the clues
+        assert!(!board.is_pencilmark_consistent());
+
+        board.propagate_all();
+        assert!(board.is_pencilmark_consistent());
+
+        // Put 2 back into R1C1, next to the 2 clue in R1C2
+        let mask = board.grid[0].candidate_mask();
+        assert_eq!(mask & 1 << 1, 0);
+        board.grid[0] = Cell::from_candidate_mask(mask | 1 << 1);
+        assert!(!board.is_pencilmark_consistent());
+    }
+
+    #[test]
+    fn candidate_total_falls_to_zero_as_the_board_is_solved() {
+        let mut board = initialized(HARD);
+        let initial = board.candidate_count_total();
+        assert_eq!(initial, 9 * (81 - board.clue_count()));
+
+        board.propagate_all();
+        let propagated = board.candidate_count_total();
+        assert!(propagated < initial);
+
+        board.solve().unwrap();
+        assert_eq!(board.candidate_count_total(), 0);
+    }
+
+    #[test]
+    fn entropy_only_falls_as_candidates_are_removed() {
+        let mut board = initialized(HARD);
+        let initial = board.entropy();
+        assert!(initial > 0.0);
+
+        board.propagate_all();
+        let propagated = board.entropy();
+        assert!(0.0 < propagated && propagated < initial);
+
+        let mut partial = board;
+        partial.try_solve_logic_only();
+        assert!(partial.entropy() <= propagated);
+
+        board.solve().unwrap();
+        assert!(board.entropy().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn restricted_digit_is_only_placed_in_allowed_cells() {
+        // The cells where the hard puzzle's solution has its 5s
+        let allowed: Vec<(usize, usize)> = HARD_SOLUTION
+            .bytes()
+            .enumerate()
+            .filter(|&(_, b)| b == b'5')
+            .map(|(idx, _)| Sudoku::idx_to_coord(idx))
+            .collect();
+
+        let mut board = initialized(HARD);
+        board.restrict_digit(5, &allowed);
+
+        let fives = board.candidates_for_value(5);
+        for (row, cols) in fives.iter().enumerate() {
+            for (col, &possible) in cols.iter().enumerate() {
+                assert!(!possible || allowed.contains(&(row, col)));
+            }
+        }
+
+        board.solve().unwrap();
+        assert!(board.is_complete_and_correct());
+
+        for (idx, cell) in board.grid.iter().enumerate() {
+            if cell.value() == Some(5) {
+                assert!(allowed.contains(&Sudoku::idx_to_coord(idx)));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn restricting_zero_panics() {
+        initialized(HARD).restrict_digit(0, &[]);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{BOARD_LEN, BOARD_SEP, BOARD_SIZE, Cell, Sudoku};
+
+/// Every ordering of three bands, stacks or lines within one
+const PERMUTATIONS: [[usize; BOARD_SEP]; 6] =
+    [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+/// Every order of the nine rows (or columns) that keeps each band (or stack)
+/// together, as the original line shown at each position
+fn line_orders() -> Vec<[usize; BOARD_LEN]> {
+    let mut orders = Vec::new();
+
+    for bands in PERMUTATIONS {
+        for first in PERMUTATIONS {
+            for second in PERMUTATIONS {
+                for third in PERMUTATIONS {
+                    let within = [first, second, third];
+
+                    orders.push(std::array::from_fn(|pos| {
+                        let (band, line) = (pos / BOARD_SEP, pos % BOARD_SEP);
+                        bands[band] * BOARD_SEP + within[band][line]
+                    }));
+                }
+            }
+        }
+    }
+
+    orders
+}
+
+/// The board seen through the given row and column orders, with digits
+/// relabeled in order of first appearance, if that is smaller than `best`
+fn relabel_if_smaller(
+    clues: &[[u8; BOARD_LEN]; BOARD_LEN],
+    rows: &[usize; BOARD_LEN],
+    cols: &[usize; BOARD_LEN],
+    best: &[u8; BOARD_SIZE],
+) -> Option<[u8; BOARD_SIZE]> {
+    let mut labels = [0; BOARD_LEN + 1];
+    let mut next_label = 1;
+
+    let mut board = [0; BOARD_SIZE];
+    let mut smaller = false;
+
+    for (pos, cell) in board.iter_mut().enumerate() {
+        let value = clues[rows[pos / BOARD_LEN]][cols[pos % BOARD_LEN]];
+
+        if value != 0 && labels[value as usize] == 0 {
+            labels[value as usize] = next_label;
+            next_label += 1;
+        }
+
+        *cell = labels[value as usize];
+
+        if !smaller {
+            if *cell > best[pos] {
+                return None;
+            }
+
+            smaller = *cell < best[pos];
+        }
+    }
+
+    smaller.then_some(board)
+}
+
+impl Sudoku {
+    /// A canonical representative of the puzzle's clues: the
+    /// lexicographically smallest board, reading empty cells as 0, reachable
+    /// by relabeling digits, reordering bands and stacks, reordering rows
+    /// within a band or columns within a stack, and transposing. Two puzzles
+    /// that are the same up to these transformations have equal canonical
+    /// forms, which makes it a key for removing duplicates from a collection.
+    /// Only clues are considered, and the result holds nothing else
+    ///
+    /// Every combination is checked, so this takes much longer than solving
+    #[must_use]
+    pub fn canonical(&self) -> Self {
+        let clues: [[u8; BOARD_LEN]; BOARD_LEN] = std::array::from_fn(|row| {
+            std::array::from_fn(|col| match self.grid[row * BOARD_LEN + col] {
+                Cell::Fixed(n) => n,
+                _ => 0,
+            })
+        });
+
+        let transposed = std::array::from_fn(|row| {
+            std::array::from_fn(|col| clues[col][row])
+        });
+
+        let orders = line_orders();
+        let mut best = [u8::MAX; BOARD_SIZE];
+
+        for grid in [clues, transposed] {
+            for rows in &orders {
+                for cols in &orders {
+                    if let Some(board) =
+                        relabel_if_smaller(&grid, rows, cols, &best)
+                    {
+                        best = board;
+                    }
+                }
+            }
+        }
+
+        let mut canonical = Self::default();
+
+        for (cell, &value) in canonical.grid.iter_mut().zip(&best) {
+            if value != 0 {
+                *cell = Cell::Fixed(value);
+            }
+        }
+
+        canonical
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Sudoku;
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    // Arto Inkala's puzzle
+    const INKALA: &str = "800000000003600000070090200050007000000045700000100030001000068008500010090000400";
+
+    #[test]
+    fn equivalent_puzzles_share_a_canonical_form() {
+        let puzzle: Sudoku = HARD.parse().unwrap();
+
+        // Transpose the puzzle, then relabel and shuffle it
+        let transposed: String = (0..81)
+            .map(|idx| char::from(HARD.as_bytes()[idx % 9 * 9 + idx / 9]))
+            .collect();
+
+        let mut variant: Sudoku = transposed.parse().unwrap();
+        variant.apply_permutation([3, 7, 1, 9, 2, 8, 5, 4, 6]);
+        variant.swap_bands(0, 2);
+        variant.swap_cols(3, 4);
+        assert!(!variant.values_eq(&puzzle));
+
+        let canonical = puzzle.canonical();
+        assert_eq!(variant.canonical().grid, canonical.grid);
+        assert_eq!(canonical.canonical().grid, canonical.grid);
+        assert_eq!(canonical.clue_count(), puzzle.clue_count());
+
+        let other: Sudoku = INKALA.parse().unwrap();
+        assert_ne!(other.canonical().grid, canonical.grid);
+    }
+}
@@ -0,0 +1,282 @@
+use std::time::Duration;
+
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+use crate::BOARD_SIZE;
+
+/// Whether [`Sudoku::solve_with_config`] may return any solution of an
+/// ambiguous puzzle
+///
+/// [`Sudoku::solve_with_config`]: crate::Sudoku::solve_with_config
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UniquenessMode {
+    /// Return the first solution found
+    #[default]
+    AnySolution,
+
+    /// Check the solution is unique first, failing with
+    /// [`SolveError::NotUnique`] if it isn't
+    ///
+    /// [`SolveError::NotUnique`]: crate::SolveError::NotUnique
+    RequireUnique,
+}
+
+/// Options controlling how [`Sudoku::solve_with_config`] searches for a
+/// solution
+///
+/// [`Sudoku::solve_with_config`]: crate::Sudoku::solve_with_config
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct SolverConfig {
+    /// The maximum number of guesses the backtracking search may make before
+    /// giving up. Unlike a time limit, this is deterministic
+    pub max_guesses: Option<usize>,
+
+    /// How long the search may run before giving up with
+    /// [`SolveError::Timeout`]. The clock is checked between deduction passes
+    /// and before each guess, so a single pass is never cut short
+    ///
+    /// [`SolveError::Timeout`]: crate::SolveError::Timeout
+    pub time_limit: Option<Duration>,
+
+    /// Allow guessing once logical deduction stalls. When disabled, a stalled
+    /// solve fails with [`SolveError::RequiresGuessing`]
+    ///
+    /// [`SolveError::RequiresGuessing`]: crate::SolveError::RequiresGuessing
+    pub backtracking: bool,
+
+    /// Whether an ambiguous puzzle is an error
+    pub uniqueness: UniquenessMode,
+
+    /// Visit cells in an order shuffled by this seed during each
+    /// deterministic pass, rather than row by row. This changes which
+    /// deductions are found first, and so the recorded steps, but never the
+    /// solution
+    pub scan_seed: Option<u64>,
+
+    /// Apply [`Sudoku::solve_naked_quads`] and [`Sudoku::solve_hidden_quads`]
+    /// during deduction. They rarely find anything the triples miss but
+    /// check far more subsets, so they are off by default
+    ///
+    /// [`Sudoku::solve_naked_quads`]: crate::Sudoku::solve_naked_quads
+    /// [`Sudoku::solve_hidden_quads`]: crate::Sudoku::solve_hidden_quads
+    pub quads: bool,
+
+    /// Apply [`Sudoku::solve_unique_rectangles`] during deduction. This relies
+    /// on the puzzle having a unique solution, so it is off by default
+    ///
+    /// [`Sudoku::solve_unique_rectangles`]:
+    /// crate::Sudoku::solve_unique_rectangles
+    pub unique_rectangles: bool,
+
+    /// Apply [`Sudoku::solve_remote_pairs`] during deduction. Building the
+    /// chains is expensive, so it is off by default
+    ///
+    /// [`Sudoku::solve_remote_pairs`]: crate::Sudoku::solve_remote_pairs
+    pub remote_pairs: bool,
+
+    /// Apply [`Sudoku::solve_xy_wings`] during deduction. Off by default, as
+    /// it compares every triple of two-candidate cells
+    ///
+    /// [`Sudoku::solve_xy_wings`]: crate::Sudoku::solve_xy_wings
+    pub xy_wings: bool,
+
+    /// Apply [`Sudoku::solve_w_wings`] during deduction. Off by default, as
+    /// it searches every unit for each pair of two-candidate cells
+    ///
+    /// [`Sudoku::solve_w_wings`]: crate::Sudoku::solve_w_wings
+    pub w_wings: bool,
+
+    /// Before trying a guess, place every naked single it leads to on a copy
+    /// of the board, and skip the guess without searching further if that
+    /// leaves a cell with no candidates. Skipped values aren't counted
+    /// towards [`SolverConfig::max_guesses`]. This never changes the
+    /// solution, only how much of the search tree is visited
+    pub forward_checking: bool,
+}
+
+impl SolverConfig {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_guesses: None,
+            time_limit: None,
+            backtracking: true,
+            uniqueness: UniquenessMode::AnySolution,
+            scan_seed: None,
+            quads: false,
+            unique_rectangles: false,
+            remote_pairs: false,
+            xy_wings: false,
+            w_wings: false,
+            forward_checking: true,
+        }
+    }
+
+    /// The order in which a deterministic pass visits the cells
+    pub(crate) fn scan_order(&self) -> [usize; BOARD_SIZE] {
+        let mut order = std::array::from_fn(|idx| idx);
+
+        if let Some(seed) = self.scan_seed {
+            order.shuffle(&mut StdRng::seed_from_u64(seed));
+        }
+
+        order
+    }
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{SolverConfig, UniquenessMode};
+    use crate::{Search, SolveError, Sudoku, Technique};
+
+    const EASY: &str = "000260701680070090190004500820100040004602900050003028009300074040050036703018000";
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    // Arto Inkala's puzzle, which needs several guesses
+    const INKALA: &str = "800000000003600000070090200050007000000045700000100030001000068008500010090000400";
+
+    /// The guesses needed to solve `puzzle`, counting only those that
+    /// weren't skipped
+    fn guesses(puzzle: &str, forward_checking: bool) -> usize {
+        let config = SolverConfig { forward_checking, ..SolverConfig::new() };
+        let mut ctx = Search { config: &config, ..Search::default() };
+
+        let mut board: Sudoku = puzzle.parse().unwrap();
+        board.initialize_superpositions();
+        board.search_first(&mut ctx).unwrap();
+
+        assert!(board.is_complete_and_correct());
+        ctx.stats[Technique::Guess as usize]
+    }
+
+    fn initialized() -> Sudoku {
+        let mut board: Sudoku = INKALA.parse().unwrap();
+        board.initialize_superpositions();
+        board
+    }
+
+    #[test]
+    fn guess_limit_is_enforced() {
+        let guesses = initialized().solve_with_stats()[&Technique::Guess];
+        assert!(guesses > 1);
+
+        let limited = SolverConfig {
+            max_guesses: Some(guesses - 1),
+            ..SolverConfig::new()
+        };
+        assert_eq!(
+            initialized().solve_with_config(&limited),
+            Err(SolveError::GuessLimitExceeded)
+        );
+
+        for max in [guesses, guesses + 1] {
+            let config =
+                SolverConfig { max_guesses: Some(max), ..SolverConfig::new() };
+            let mut board = initialized();
+
+            assert_eq!(board.solve_with_config(&config), Ok(()));
+            assert!(board.is_complete_and_correct());
+        }
+    }
+
+    #[test]
+    fn time_limit_is_enforced() {
+        let expired = SolverConfig {
+            time_limit: Some(Duration::ZERO),
+            ..SolverConfig::new()
+        };
+        assert_eq!(
+            initialized().solve_with_config(&expired),
+            Err(SolveError::Timeout)
+        );
+
+        let generous = SolverConfig {
+            time_limit: Some(Duration::from_secs(30)),
+            ..SolverConfig::new()
+        };
+        let mut board = initialized();
+
+        assert_eq!(board.solve_with_config(&generous), Ok(()));
+        assert!(board.is_complete_and_correct());
+    }
+
+    #[test]
+    fn forward_checking_makes_fewer_guesses() {
+        // The hard grid's one guess is right first time, leaving nothing to
+        // prune, but Inkala's puzzle has dead ends that forward checking
+        // rules out without searching them
+        assert_eq!(guesses(HARD, true), guesses(HARD, false));
+        assert!(guesses(INKALA, true) < guesses(INKALA, false));
+    }
+
+    #[test]
+    fn require_unique_rejects_only_ambiguous_puzzles() {
+        let require_unique = SolverConfig {
+            uniqueness: UniquenessMode::RequireUnique,
+            ..SolverConfig::new()
+        };
+
+        // Without its first clue the easy puzzle has several solutions
+        let mut ambiguous: Sudoku = EASY.parse().unwrap();
+        assert!(ambiguous.remove_clue(0, 3));
+        ambiguous.initialize_superpositions();
+
+        for config in [&SolverConfig::new(), &require_unique] {
+            let mut board = initialized();
+            assert_eq!(board.solve_with_config(config), Ok(()));
+            assert!(board.is_complete_and_correct());
+        }
+
+        let mut board = ambiguous;
+        assert_eq!(board.solve_with_config(&SolverConfig::new()), Ok(()));
+        assert!(board.is_complete_and_correct());
+
+        let mut board = ambiguous;
+        assert_eq!(
+            board.solve_with_config(&require_unique),
+            Err(SolveError::NotUnique)
+        );
+        assert!(!board.is_solved());
+    }
+
+    #[test]
+    fn scan_order_changes_the_steps_but_not_the_solution() {
+        let solve = |scan_seed| {
+            let config = SolverConfig { scan_seed, ..SolverConfig::new() };
+            let mut ctx = Search {
+                config: &config,
+                steps: Some(Vec::new()),
+                ..Search::default()
+            };
+
+            let mut board: Sudoku = EASY.parse().unwrap();
+            board.initialize_superpositions();
+            board.search_first(&mut ctx).unwrap();
+
+            (board, ctx.steps.unwrap())
+        };
+
+        let (expected, unshuffled) = solve(None);
+        let (first, first_steps) = solve(Some(1));
+        let (second, second_steps) = solve(Some(2));
+
+        assert_eq!(first.grid, expected.grid);
+        assert_eq!(second.grid, expected.grid);
+
+        assert_ne!(first_steps, unshuffled);
+        assert_ne!(first_steps, second_steps);
+
+        // The same seed always gives the same order
+        assert_eq!(solve(Some(1)).1, first_steps);
+    }
+}
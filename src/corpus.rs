@@ -0,0 +1,142 @@
+use std::io::{self, BufRead};
+
+use crate::{SolveError, Sudoku};
+
+/// The outcome of solving one puzzle from a corpus read by
+/// [`Sudoku::verify_corpus`]
+#[derive(Debug, Clone, Copy)]
+pub struct CorpusResult {
+    /// The line of the corpus the puzzle came from, starting at 1
+    pub line: usize,
+
+    /// The solution given in the corpus
+    pub expected: Sudoku,
+
+    /// What the solver produced
+    pub outcome: Result<Sudoku, SolveError>,
+}
+
+impl CorpusResult {
+    /// Whether the solver found the expected solution
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.outcome.is_ok_and(|solution| solution.values_eq(&self.expected))
+    }
+}
+
+impl Sudoku {
+    /// Reads a corpus of puzzles with their solutions, one `puzzle,solution`
+    /// pair per line, each in the format accepted by [`Sudoku::from_str`].
+    /// Blank lines are skipped. Returns each pair with its line number,
+    /// starting at 1
+    ///
+    /// # Errors
+    ///
+    /// Fails if reading fails, or with [`io::ErrorKind::InvalidData`] if a
+    /// line isn't a pair of valid puzzles
+    pub fn read_corpus(
+        reader: impl BufRead,
+    ) -> io::Result<Vec<(usize, Self, Self)>> {
+        let mut corpus = Vec::new();
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let Some((puzzle, solution)) = line.split_once(',') else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "line {} isn't a `puzzle,solution` pair",
+                        number + 1
+                    ),
+                ));
+            };
+
+            let parse = |s: &str| {
+                s.parse::<Self>()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            };
+
+            corpus.push((number + 1, parse(puzzle)?, parse(solution)?));
+        }
+
+        Ok(corpus)
+    }
+
+    /// Solves every puzzle in a corpus read by [`Sudoku::read_corpus`] and
+    /// compares the result with the expected solution, for regression testing
+    /// against published puzzle sets
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Sudoku::read_corpus`] would
+    pub fn verify_corpus(
+        reader: impl BufRead,
+    ) -> io::Result<Vec<CorpusResult>> {
+        Ok(Self::read_corpus(reader)?
+            .into_iter()
+            .map(|(line, puzzle, expected)| CorpusResult {
+                line,
+                expected,
+                outcome: puzzle.solve_into(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, ErrorKind};
+
+    use crate::{SolveError, Sudoku};
+
+    const CORPUS: &str = "\
+020000000000600003074080000000003002080040010600500000000010780500009000000000040,126437958895621473374985126457193862983246517612578394269314785548769231731852649
+
+800000000003600000070090200050007000000045700000100030001000068008500010090000400,812753649943682175675491283154237896369845721287169534521974368438526917796318452
+020000000000600003074080000000003002080040010600500000000010780500009000000000040,216437958895621473374985126457193862983246517612578394269314785548769231731852649
+";
+
+    #[test]
+    fn corpus_solutions_are_checked_against_the_answers() {
+        let results = Sudoku::verify_corpus(Cursor::new(CORPUS)).unwrap();
+
+        let lines: Vec<usize> = results.iter().map(|r| r.line).collect();
+        assert_eq!(lines, [1, 3, 4]);
+
+        assert!(results[0].passed());
+        assert!(results[1].passed());
+
+        // The last answer has its first two values swapped
+        assert!(results[2].outcome.is_ok());
+        assert!(!results[2].passed());
+    }
+
+    #[test]
+    fn unsolvable_corpus_puzzles_fail() {
+        let contradictory =
+            format!("12345678.{}9{}", ".".repeat(44), ".".repeat(27));
+        let line = format!("{contradictory},{contradictory}");
+
+        let [result] = &Sudoku::verify_corpus(Cursor::new(line)).unwrap()[..]
+        else {
+            panic!("expected one result");
+        };
+
+        assert!(matches!(result.outcome, Err(SolveError::NoSolution)));
+        assert!(!result.passed());
+    }
+
+    #[test]
+    fn malformed_corpus_lines_are_rejected() {
+        let unpaired = Sudoku::read_corpus(Cursor::new("1".repeat(81)));
+        assert_eq!(unpaired.unwrap_err().kind(), ErrorKind::InvalidData);
+
+        let short = Sudoku::read_corpus(Cursor::new("123,456"));
+        assert_eq!(short.unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+}
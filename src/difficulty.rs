@@ -0,0 +1,60 @@
+use crate::{Sudoku, Technique};
+
+/// How hard a puzzle is to solve, judged by the hardest technique it needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Difficulty {
+    /// Solvable with naked and hidden singles alone
+    Easy,
+
+    /// Needs intermediate eliminations such as locked candidates or triples
+    Medium,
+
+    /// Needs an advanced technique or guessing
+    Hard,
+}
+
+impl Technique {
+    /// The difficulty of a puzzle whose hardest required technique is `self`
+    #[must_use]
+    pub const fn difficulty(self) -> Difficulty {
+        match self {
+            Self::NakedSingle | Self::HiddenSingle => Difficulty::Easy,
+            Self::Pointing
+            | Self::Claiming
+            | Self::NakedTriple
+            | Self::HiddenTriple => Difficulty::Medium,
+            Self::NakedQuad
+            | Self::HiddenQuad
+            | Self::UniqueRectangle
+            | Self::RemotePair
+            | Self::XyWing
+            | Self::WWing
+            | Self::Guess => Difficulty::Hard,
+        }
+    }
+}
+
+impl Sudoku {
+    /// Rates the puzzle by the hardest technique on its solving path, using
+    /// the default [`SolverConfig`]. Returns `None` if it has no solution
+    ///
+    /// [`SolverConfig`]: crate::SolverConfig
+    #[must_use]
+    pub fn rate(&self) -> Option<Difficulty> {
+        let mut board = *self;
+        board.initialize_superpositions();
+
+        let path = board.solve_path();
+
+        if !board.is_complete_and_correct() {
+            return None;
+        }
+
+        Some(
+            path.into_iter()
+                .map(Technique::difficulty)
+                .max()
+                .unwrap_or(Difficulty::Easy),
+        )
+    }
+}
@@ -0,0 +1,646 @@
+use std::fmt;
+
+use colored::{Color, ColoredString, Colorize};
+
+use crate::{BOARD_LEN, BOARD_SEP, Cell, Sudoku};
+
+/// The characters used to draw the lines between subsections
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BorderStyle {
+    /// `+-------+` and `|`
+    #[default]
+    Ascii,
+
+    /// Box-drawing characters, e.g. `┌───────┬`, `├───────┼` and `│`
+    Unicode,
+
+    /// No lines at all. Subsections are separated by whitespace only
+    None,
+}
+
+struct Borders {
+    top: Option<&'static str>,
+    middle: &'static str,
+    bottom: Option<&'static str>,
+    vertical: &'static str,
+}
+
+impl BorderStyle {
+    const fn borders(self) -> Borders {
+        match self {
+            Self::Ascii => Borders {
+                top: Some("+-------+-------+-------+"),
+                middle: "+-------+-------+-------+",
+                bottom: Some("+-------+-------+-------+"),
+                vertical: "|",
+            },
+            Self::Unicode => Borders {
+                top: Some("┌───────┬───────┬───────┐"),
+                middle: "├───────┼───────┼───────┤",
+                bottom: Some("└───────┴───────┴───────┘"),
+                vertical: "│",
+            },
+            Self::None => {
+                Borders { top: None, middle: "", bottom: None, vertical: " " }
+            }
+        }
+    }
+}
+
+/// The colours used for each kind of cell
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorTheme {
+    /// Green clues, yellow solver placements and red unfilled cells
+    #[default]
+    Default,
+
+    /// The bright variants of the default colours, for dark backgrounds
+    HighContrast,
+
+    /// No colours at all
+    Monochrome,
+
+    /// Colours from the Solarized palette, written as 24-bit colour codes
+    Solarized,
+
+    /// Blue clues, orange solver placements and purple unfilled cells from
+    /// the Okabe-Ito palette, which stay apart under common forms of colour
+    /// blindness. Clues are also bold and placements underlined, so the
+    /// kinds of cell can be told apart without relying on colour at all
+    Accessible,
+}
+
+impl ColorTheme {
+    /// The colours of clues, solver placements and unfilled cells, or `None`
+    /// if nothing should be coloured
+    const fn palette(self) -> Option<[Color; 3]> {
+        match self {
+            Self::Default => Some([Color::Green, Color::Yellow, Color::Red]),
+            Self::HighContrast => Some([
+                Color::BrightGreen,
+                Color::BrightYellow,
+                Color::BrightRed,
+            ]),
+            Self::Monochrome => None,
+            Self::Solarized => Some([
+                Color::TrueColor { r: 133, g: 153, b: 0 },
+                Color::TrueColor { r: 181, g: 137, b: 0 },
+                Color::TrueColor { r: 220, g: 50, b: 47 },
+            ]),
+            Self::Accessible => Some([
+                Color::TrueColor { r: 0, g: 114, b: 178 },
+                Color::TrueColor { r: 230, g: 159, b: 0 },
+                Color::TrueColor { r: 204, g: 121, b: 167 },
+            ]),
+        }
+    }
+
+    /// The colours of cells in the frontier view with one candidate (or
+    /// none), two, and more, or `None` if nothing should be coloured
+    const fn frontier_palette(self) -> Option<[Color; 3]> {
+        match self {
+            Self::Default => Some([Color::Red, Color::Yellow, Color::Blue]),
+            Self::HighContrast => {
+                Some([Color::BrightRed, Color::BrightYellow, Color::BrightBlue])
+            }
+            Self::Monochrome => None,
+            Self::Solarized => Some([
+                Color::TrueColor { r: 220, g: 50, b: 47 },
+                Color::TrueColor { r: 181, g: 137, b: 0 },
+                Color::TrueColor { r: 38, g: 139, b: 210 },
+            ]),
+            Self::Accessible => Some([
+                Color::TrueColor { r: 213, g: 94, b: 0 },
+                Color::TrueColor { r: 230, g: 159, b: 0 },
+                Color::TrueColor { r: 86, g: 180, b: 233 },
+            ]),
+        }
+    }
+
+    /// Styles `text` as the kind of cell at `slot` in `palette`, which is
+    /// one of the theme's palettes, leaving it plain if there is none
+    fn paint(
+        self,
+        text: &str,
+        palette: Option<[Color; 3]>,
+        slot: usize,
+    ) -> ColoredString {
+        palette.map_or_else(
+            || text.normal(),
+            |palette| self.emphasise(text.color(palette[slot]), slot),
+        )
+    }
+
+    /// Adds the styling other than colour that the theme gives to clues,
+    /// solver placements or unfilled cells, by their position in
+    /// [`ColorTheme::palette`], or to frontier cells by their position in
+    /// [`ColorTheme::frontier_palette`]
+    fn emphasise(self, text: ColoredString, slot: usize) -> ColoredString {
+        match (self, slot) {
+            (Self::Accessible, 0) => text.bold(),
+            (Self::Accessible, 1) => text.underline(),
+            _ => text,
+        }
+    }
+}
+
+/// Controls how a [`Sudoku`] is rendered by [`Sudoku::display`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayOptions {
+    pub border: BorderStyle,
+
+    /// Label the columns `A`-`I` along the top and the rows `1`-`9` down the
+    /// left, so cells can be referred to like `E5`
+    pub labels: bool,
+
+    /// Show how constrained each cell is instead of its value. See
+    /// [`Sudoku::display_frontier`]
+    pub frontier: bool,
+
+    /// The colours used for each cell, which in the frontier view depend on
+    /// the number of candidates instead
+    pub theme: ColorTheme,
+}
+
+/// A [`Sudoku`] paired with the options used to render it
+#[derive(Debug, Clone, Copy)]
+pub struct SudokuDisplay<'a> {
+    sudoku: &'a Sudoku,
+    options: DisplayOptions,
+}
+
+impl Sudoku {
+    /// Renders the board with the given options, e.g.
+    /// `println!("{}", sudoku.display(options))`
+    #[must_use]
+    pub const fn display(&self, options: DisplayOptions) -> SudokuDisplay<'_> {
+        SudokuDisplay { sudoku: self, options }
+    }
+}
+
+impl Sudoku {
+    /// Renders the board for debugging the solver, showing each cell with a
+    /// superposition as its number of candidates, coloured red for one (or a
+    /// contradictory zero), yellow for two and blue for more. Filled cells are
+    /// shown as `.`. Other themes can be used by setting
+    /// [`DisplayOptions::frontier`] with [`Sudoku::display`]
+    #[must_use]
+    pub const fn display_frontier(&self) -> SudokuDisplay<'_> {
+        self.display(DisplayOptions {
+            border: BorderStyle::Ascii,
+            labels: false,
+            frontier: true,
+            theme: ColorTheme::Default,
+        })
+    }
+}
+
+/// A cell rendered by its number of candidates, for
+/// [`Sudoku::display_frontier`]
+struct Frontier<'a>(&'a Cell, ColorTheme);
+
+impl Frontier<'_> {
+    fn styled(&self) -> ColoredString {
+        let Some(count) = self.0.count_superstates() else {
+            return match self.0 {
+                Cell::Empty => " ".normal(),
+                _ => ".".normal(),
+            };
+        };
+
+        let slot = count.clamp(1, 3) - 1;
+        self.1.paint(&count.to_string(), self.1.frontier_palette(), slot)
+    }
+}
+
+impl fmt::Display for Frontier<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.styled())
+    }
+}
+
+/// A cell rendered by its value in the colours of a [`ColorTheme`]
+struct Themed<'a>(&'a Cell, ColorTheme);
+
+impl Themed<'_> {
+    fn styled(&self) -> ColoredString {
+        let (text, slot) = match self.0 {
+            Cell::Empty => return " ".normal(),
+            Cell::Fixed(n) => (n.to_string(), 0),
+            Cell::Collapsed(n) => (n.to_string(), 1),
+            Cell::Superposition(_) => ("+".to_string(), 2),
+        };
+
+        self.1.paint(&text, self.1.palette(), slot)
+    }
+}
+
+impl fmt::Display for Themed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.styled())
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Themed(self, ColorTheme::Default).fmt(f)
+    }
+}
+
+impl Sudoku {
+    /// Renders the board with the given options straight into `f`, as
+    /// [`Sudoku::display`] would, without building a `String` first
+    ///
+    /// # Errors
+    ///
+    /// Fails if writing to `f` fails
+    pub fn write_to(
+        &self,
+        f: &mut impl fmt::Write,
+        options: &DisplayOptions,
+    ) -> fmt::Result {
+        let Borders { top, middle, bottom, vertical } =
+            options.border.borders();
+
+        // Width of the row labels, so the borders line up underneath them
+        let gutter = if options.labels { "  " } else { "" };
+
+        if options.labels {
+            let mut header = String::from(gutter);
+
+            for (col, label) in ('A'..='I').enumerate() {
+                if col % BOARD_SEP == 0 {
+                    header.push_str("  ");
+                }
+
+                header.push(label);
+                header.push(' ');
+            }
+
+            writeln!(f, "{}", header.trim_end())?;
+        }
+
+        if let Some(top) = top {
+            writeln!(f, "{gutter}{top}")?;
+        }
+
+        for (row, cells) in self.grid.chunks(BOARD_LEN).enumerate() {
+            if row > 0 && row % BOARD_SEP == 0 {
+                if middle.is_empty() {
+                    writeln!(f)?;
+                } else {
+                    writeln!(f, "{gutter}{middle}")?;
+                }
+            }
+
+            if options.labels {
+                write!(f, "{} ", row + 1)?;
+            }
+
+            for (col, cell) in cells.iter().enumerate() {
+                if col % BOARD_SEP == 0 {
+                    write!(f, "{vertical} ")?;
+                }
+
+                if options.frontier {
+                    write!(f, "{} ", Frontier(cell, options.theme))?;
+                } else {
+                    write!(f, "{} ", Themed(cell, options.theme))?;
+                }
+            }
+
+            write!(f, "{vertical}")?;
+
+            if row + 1 < BOARD_LEN {
+                // Rows inside a band end with a space after the border
+                if row % BOARD_SEP != BOARD_SEP - 1 {
+                    write!(f, " ")?;
+                }
+
+                writeln!(f)?;
+            }
+        }
+
+        if let Some(bottom) = bottom {
+            write!(f, "\n{gutter}{bottom}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for SudokuDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.sudoku.write_to(f, &self.options)
+    }
+}
+
+impl fmt::Display for Sudoku {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(DisplayOptions::default()).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::{self, Write};
+
+    use colored::{Color, Styles};
+
+    use super::{BorderStyle, ColorTheme, DisplayOptions, Frontier, Themed};
+    use crate::{BOARD_LEN, BOARD_SEP, Cell, Sudoku};
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    /// The board as it was rendered before border styles existed
+    fn original(sudoku: &Sudoku) -> String {
+        const ROW_SEP: &str = "+-------+-------+-------+";
+
+        let mut out = String::new();
+
+        for (idx, cell) in sudoku.grid.iter().enumerate() {
+            if idx % (BOARD_LEN * BOARD_SEP) == 0 {
+                if idx > 0 {
+                    writeln!(out, "|").unwrap();
+                }
+
+                write!(out, "{ROW_SEP}\n| ").unwrap();
+            } else if idx % BOARD_SEP == 0 {
+                write!(out, "| ").unwrap();
+
+                if idx % BOARD_LEN == 0 {
+                    write!(out, "\n| ").unwrap();
+                }
+            }
+
+            write!(out, "{cell} ").unwrap();
+        }
+
+        write!(out, "|\n{ROW_SEP}").unwrap();
+        out
+    }
+
+    #[test]
+    fn ascii_matches_original_rendering() {
+        let mut board: Sudoku = HARD.parse().unwrap();
+        assert_eq!(board.to_string(), original(&board));
+
+        board.initialize_superpositions();
+        board.try_solve_logic_only();
+        assert_eq!(board.to_string(), original(&board));
+
+        board.solve().unwrap();
+        assert_eq!(board.to_string(), original(&board));
+    }
+
+    #[test]
+    fn unicode_draws_box_characters() {
+        let board: Sudoku = HARD.parse().unwrap();
+        let options = DisplayOptions {
+            border: BorderStyle::Unicode,
+            ..Default::default()
+        };
+        let text = board.display(options).to_string();
+
+        assert!(text.contains('┼'));
+        assert!(text.starts_with('┌'));
+        assert!(!text.contains('+'));
+    }
+
+    #[test]
+    fn labels_add_a_header_and_prefix_each_row() {
+        let board: Sudoku = HARD.parse().unwrap();
+        let options = DisplayOptions {
+            labels: true,
+            theme: ColorTheme::Monochrome,
+            ..Default::default()
+        };
+        let text = board.display(options).to_string();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "    A B C   D E F   G H I");
+
+        // Borders are indented to line up under the labels
+        assert_eq!(lines[1], "  +-------+-------+-------+");
+        assert_eq!(lines.last().unwrap(), &lines[1]);
+
+        let rows: Vec<&str> =
+            lines.iter().copied().filter(|line| !line.contains('+')).collect();
+        assert_eq!(rows.len(), 10);
+
+        for (label, row) in (1..).zip(&rows[1..]) {
+            assert!(row.starts_with(&format!("{label} | ")), "{row}");
+        }
+
+        assert_eq!(rows[1].trim_end(), "1 |   2   |       |       |");
+    }
+
+    /// The hard puzzle after its clues have been removed from their peers'
+    /// candidates
+    fn propagated() -> Sudoku {
+        let mut board: Sudoku = HARD.parse().unwrap();
+        board.initialize_superpositions();
+        board.propagate_all();
+        board
+    }
+
+    #[test]
+    fn frontier_counts_candidates() {
+        let options = DisplayOptions {
+            frontier: true,
+            theme: ColorTheme::Monochrome,
+            ..Default::default()
+        };
+
+        let text = propagated().display(options).to_string();
+
+        assert_eq!(
+            text.lines().map(str::trim_end).collect::<Vec<_>>(),
+            [
+                "+-------+-------+-------+",
+                "| 4 . 6 | 5 4 4 | 6 4 7 |",
+                "| 3 3 4 | . 4 5 | 6 4 . |",
+                "| 3 . . | 4 . 3 | 5 4 4 |",
+                "+-------+-------+-------+",
+                "| 4 4 4 | 4 3 . | 5 4 . |",
+                "| 4 . 5 | 3 . 3 | 4 . 4 |",
+                "| . 4 5 | . 3 4 | 4 3 4 |",
+                "+-------+-------+-------+",
+                "| 4 4 4 | 3 . 4 | . . 3 |",
+                "| . 4 6 | 5 4 . | 4 3 2 |",
+                "| 6 4 7 | 4 5 5 | 6 . 4 |",
+                "+-------+-------+-------+",
+            ]
+        );
+    }
+
+    #[test]
+    fn frontier_uses_the_theme() {
+        let board = propagated();
+
+        let colours = |theme| {
+            board
+                .grid
+                .iter()
+                .map(|cell| Frontier(cell, theme).styled())
+                .filter(|text| text.input != ".")
+                .map(|text| (text.input, text.fgcolor))
+                .collect::<Vec<_>>()
+        };
+
+        let default = colours(ColorTheme::Default);
+        assert!(default.contains(&("2".to_string(), Some(Color::Yellow))));
+        assert!(default.contains(&("4".to_string(), Some(Color::Blue))));
+
+        let bright = colours(ColorTheme::HighContrast);
+        assert!(bright.contains(&("2".to_string(), Some(Color::BrightYellow))));
+        assert!(bright.contains(&("4".to_string(), Some(Color::BrightBlue))));
+
+        assert!(
+            colours(ColorTheme::Monochrome)
+                .iter()
+                .all(|(_, colour)| colour.is_none())
+        );
+        assert_ne!(colours(ColorTheme::Solarized), default);
+
+        // The default frontier view keeps the default theme
+        assert_eq!(
+            board.display_frontier().to_string(),
+            board
+                .display(DisplayOptions {
+                    frontier: true,
+                    ..Default::default()
+                })
+                .to_string()
+        );
+    }
+
+    /// The hard puzzle as far as logic alone takes it, holding clues, solver
+    /// placements and cells with candidates left
+    fn mid_solve() -> Sudoku {
+        let mut board: Sudoku = HARD.parse().unwrap();
+        board.initialize_superpositions();
+        board.try_solve_logic_only();
+        board
+    }
+
+    /// The text and colour of each cell of [`mid_solve`] in `theme`
+    fn themed(theme: ColorTheme) -> Vec<(String, Option<Color>)> {
+        mid_solve()
+            .grid
+            .iter()
+            .map(|cell| {
+                let text = Themed(cell, theme).styled();
+                (text.input, text.fgcolor)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn monochrome_has_no_colour_codes() {
+        let board = mid_solve();
+
+        for frontier in [false, true] {
+            let options = DisplayOptions {
+                frontier,
+                theme: ColorTheme::Monochrome,
+                ..Default::default()
+            };
+
+            assert!(!board.display(options).to_string().contains('\x1b'));
+        }
+
+        assert!(
+            themed(ColorTheme::Monochrome)
+                .iter()
+                .all(|(_, colour)| colour.is_none())
+        );
+    }
+
+    #[test]
+    fn high_contrast_uses_bright_colours() {
+        let default = themed(ColorTheme::Default);
+        let bright = themed(ColorTheme::HighContrast);
+
+        for ((text, colour), (bright_text, bright_colour)) in
+            default.iter().zip(&bright)
+        {
+            assert_eq!(text, bright_text);
+
+            let expected = colour.map(|colour| match colour {
+                Color::Green => Color::BrightGreen,
+                Color::Yellow => Color::BrightYellow,
+                Color::Red => Color::BrightRed,
+                _ => panic!("unexpected default colour {colour:?}"),
+            });
+
+            assert_eq!(*bright_colour, expected);
+        }
+
+        for colour in
+            [Color::BrightGreen, Color::BrightYellow, Color::BrightRed]
+        {
+            assert!(bright.iter().any(|(_, c)| *c == Some(colour)));
+        }
+    }
+
+    #[test]
+    fn accessible_styles_cells_beyond_colour() {
+        let board = mid_solve();
+
+        for cell in &board.grid {
+            let text = Themed(cell, ColorTheme::Accessible).styled();
+            let (bold, underline) = (
+                text.style.contains(Styles::Bold),
+                text.style.contains(Styles::Underline),
+            );
+
+            assert!(text.fgcolor.is_some());
+
+            match cell {
+                Cell::Fixed(_) => assert!(bold && !underline),
+                Cell::Collapsed(_) => assert!(underline && !bold),
+                _ => assert!(!bold && !underline),
+            }
+
+            // No other theme adds these
+            let plain = Themed(cell, ColorTheme::Default).styled();
+            assert_eq!(plain.style, Styles::Clear.into());
+        }
+    }
+
+    /// A [`fmt::Write`] over raw bytes, standing in for a byte buffer or
+    /// stream
+    struct Bytes(Vec<u8>);
+
+    impl Write for Bytes {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.extend_from_slice(s.as_bytes());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_to_matches_display() {
+        let board = mid_solve();
+
+        for options in [
+            DisplayOptions::default(),
+            DisplayOptions {
+                border: BorderStyle::Unicode,
+                labels: true,
+                ..Default::default()
+            },
+        ] {
+            let expected = board.display(options).to_string();
+
+            let mut text = String::new();
+            board.write_to(&mut text, &options).unwrap();
+            assert_eq!(text, expected);
+
+            let mut bytes = Bytes(Vec::new());
+            board.write_to(&mut bytes, &options).unwrap();
+            assert_eq!(bytes.0, expected.as_bytes());
+        }
+    }
+}
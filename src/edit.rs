@@ -0,0 +1,264 @@
+use std::iter;
+
+use crate::{BOARD_LEN, BOARD_SIZE, Cell, SolveError, Sudoku};
+
+impl Sudoku {
+    /// A board with no clues, to be filled in with [`Sudoku::with_clue`]
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Returns the board with the clue at `(row, col)` set to `value`, for
+    /// building puzzles fluently. See [`Sudoku::set`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinate is off the board or `value` is greater than 9
+    #[must_use]
+    pub fn with_clue(mut self, row: usize, col: usize, value: u8) -> Self {
+        self.set(row, col, value);
+        self
+    }
+
+    /// Sets the clue at `(row, col)` to `value`, or clears the cell if `value`
+    /// is 0. Candidates elsewhere on the board aren't updated; see
+    /// [`Sudoku::resolve_after_change`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinate is off the board or `value` is greater than 9
+    pub fn set(&mut self, row: usize, col: usize, value: u8) {
+        assert!(usize::from(value) <= BOARD_LEN, "Value out of range");
+
+        self.grid[Self::checked_coord_to_idx((row, col))] =
+            if value == 0 { Cell::Empty } else { Cell::Fixed(value) };
+    }
+
+    /// Turns the clue at `(row, col)` back into an empty cell, returning
+    /// whether there was a clue to remove. Cells filled in by the solver are
+    /// left alone, as are candidates elsewhere on the board
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinate is off the board
+    pub fn remove_clue(&mut self, row: usize, col: usize) -> bool {
+        let cell = &mut self.grid[Self::checked_coord_to_idx((row, col))];
+
+        if !matches!(cell, Cell::Fixed(_)) {
+            return false;
+        }
+
+        *cell = Cell::Empty;
+        true
+    }
+
+    /// A puzzle made from this board by keeping only the cells in `keep` as
+    /// clues, with every other cell empty. Unfilled cells in `keep` stay
+    /// empty. Masking a solution with enough cells kept gives a puzzle that
+    /// solves back to it
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate in `keep` is off the board
+    #[must_use]
+    pub fn mask_solution(&self, keep: &[(usize, usize)]) -> Self {
+        let mut puzzle = Self { grid: [Cell::Empty; BOARD_SIZE], ..*self };
+
+        for &cell in keep {
+            let idx = Self::checked_coord_to_idx(cell);
+
+            if let Some(n) = self.grid[idx].value() {
+                puzzle.grid[idx] = Cell::Fixed(n);
+            }
+        }
+
+        puzzle
+    }
+
+    /// Solves the board again after the clue at `changed` was added, removed
+    /// or edited. Only the changed cell and its peers have their candidates
+    /// recomputed; every other cell keeps what an earlier solve or
+    /// [`Sudoku::initialize_superpositions`] left there. Values filled in and
+    /// candidates eliminated elsewhere may depend on the old clue, so if the
+    /// kept state can't be completed, everything but the clues is reset and
+    /// the board is solved from scratch. On puzzles with several solutions,
+    /// the one found can differ from a fresh solve's
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Sudoku::solve`] would
+    ///
+    /// # Panics
+    ///
+    /// Panics if `changed` is off the board
+    pub fn resolve_after_change(
+        &mut self,
+        changed: (usize, usize),
+    ) -> Result<(), SolveError> {
+        let idx = Self::checked_coord_to_idx(changed);
+        let mut board = *self;
+
+        for affected in iter::once(idx).chain(self.all_peers(idx)) {
+            if !matches!(board.grid[affected], Cell::Fixed(_)) {
+                board.grid[affected] = Cell::Empty;
+            }
+        }
+
+        board.initialize_superpositions();
+
+        if board.solve().is_ok() {
+            *self = board;
+            return Ok(());
+        }
+
+        for cell in &mut self.grid {
+            if !matches!(cell, Cell::Fixed(_)) {
+                *cell = Cell::Empty;
+            }
+        }
+
+        self.initialize_superpositions();
+        self.solve()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BOARD_SIZE, Cell, Sudoku};
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    fn values(board: &Sudoku) -> Vec<Option<u8>> {
+        board.grid.iter().map(Cell::value).collect()
+    }
+
+    #[test]
+    fn resolve_after_change_matches_fresh_solve() {
+        let mut solution: Sudoku = HARD.parse().unwrap();
+        solution.initialize_superpositions();
+        solution.solve().unwrap();
+        let value = solution.grid[2].value().unwrap();
+
+        let mut board: Sudoku = HARD.parse().unwrap();
+        board.initialize_superpositions();
+        board.try_solve_logic_only();
+
+        board.set(0, 2, value);
+        board.resolve_after_change((0, 2)).unwrap();
+
+        let mut expected: Sudoku = HARD.parse().unwrap();
+        expected.set(0, 2, value);
+        expected.initialize_superpositions();
+        expected.solve().unwrap();
+
+        assert!(board.is_complete_and_correct());
+        assert_eq!(values(&board), values(&expected));
+    }
+
+    #[test]
+    fn resolve_after_change_keeps_state_away_from_the_change() {
+        let mut fresh = Sudoku::empty().with_clue(0, 0, 1);
+        fresh.initialize_superpositions();
+        fresh.solve().unwrap();
+        assert_ne!(fresh.grid[80].value(), Some(9));
+
+        let mut board = Sudoku::empty();
+        board.initialize_superpositions();
+
+        // A candidate set far from the edited cell that an earlier solve
+        // could have left, which still allows a solution
+        board.grid[80] = Cell::Superposition(std::array::from_fn(|i| i == 8));
+
+        board.set(0, 0, 1);
+        board.resolve_after_change((0, 0)).unwrap();
+
+        assert!(board.is_complete_and_correct());
+        assert_eq!(board.grid[0].value(), Some(1));
+        assert_eq!(board.grid[80].value(), Some(9));
+    }
+
+    #[test]
+    fn resolve_after_change_discards_stale_candidates() {
+        let mut board = Sudoku::empty();
+        board.initialize_superpositions();
+
+        // Candidates left over from an earlier board, far from the edited
+        // cell, that rule out every solution
+        let only_one = Cell::Superposition(std::array::from_fn(|i| i == 0));
+        board.grid[70] = only_one;
+        board.grid[80] = only_one;
+
+        board.set(0, 0, 1);
+        board.resolve_after_change((0, 0)).unwrap();
+
+        assert!(board.is_complete_and_correct());
+        assert_eq!(board.grid[0].value(), Some(1));
+    }
+
+    #[test]
+    fn resolve_after_removing_clue_keeps_a_solution() {
+        let mut board: Sudoku = HARD.parse().unwrap();
+        board.initialize_superpositions();
+        board.solve().unwrap();
+
+        assert!(board.remove_clue(0, 1));
+        board.resolve_after_change((0, 1)).unwrap();
+
+        assert!(board.is_complete_and_correct());
+    }
+
+    #[test]
+    fn with_clue_builds_the_same_board_as_a_zero_grid() {
+        let mut grid = [[0; 9]; 9];
+        let mut built = Sudoku::empty();
+
+        for (idx, c) in HARD.bytes().enumerate() {
+            let (row, col) = Sudoku::idx_to_coord(idx);
+            let value = c - b'0';
+
+            grid[row][col] = value;
+            if value != 0 {
+                built = built.with_clue(row, col, value);
+            }
+        }
+
+        assert_eq!(built.grid, Sudoku::from_zero_grid(&grid).grid);
+    }
+
+    #[test]
+    fn remove_clue_only_removes_clues() {
+        let mut board: Sudoku = HARD.parse().unwrap();
+
+        assert!(board.remove_clue(0, 1));
+        assert!(matches!(board.grid[1], Cell::Empty));
+        assert!(!board.remove_clue(0, 1));
+
+        assert!(!board.remove_clue(0, 0));
+        assert!(matches!(board.grid[0], Cell::Empty));
+
+        // Values the solver filled in aren't clues
+        let mut solved = board.solve_into().unwrap();
+        assert!(!solved.remove_clue(0, 0));
+        assert!(solved.grid[0].value().is_some());
+    }
+
+    #[test]
+    fn masked_solution_solves_back_to_it() {
+        let puzzle: Sudoku = HARD.parse().unwrap();
+        let solution = puzzle.solve_into().unwrap();
+
+        let clues: Vec<(usize, usize)> = (0..BOARD_SIZE)
+            .filter(|&idx| puzzle.grid[idx].value().is_some())
+            .map(Sudoku::idx_to_coord)
+            .collect();
+
+        let masked = solution.mask_solution(&clues);
+        assert_eq!(masked.grid, puzzle.grid);
+        assert!(masked.solve_into().unwrap().values_eq(&solution));
+
+        // Cells kept from an unfilled board stay empty
+        let blank = Sudoku::empty().mask_solution(&[(0, 0)]);
+        assert!(matches!(blank.grid[0], Cell::Empty));
+    }
+}
@@ -0,0 +1,263 @@
+use std::fmt;
+
+use crate::{Parity, UnitKind};
+
+/// A failure to read a puzzle from its textual representation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input didn't contain exactly `expected` cells
+    WrongLength { found: usize, expected: usize },
+
+    /// `ch` at position `pos` is neither a digit nor an empty-cell marker
+    InvalidChar { pos: usize, ch: char },
+
+    /// The cell at position `pos` holds `value`, which isn't in `0..=9`
+    OutOfRange { pos: usize, value: u8 },
+
+    /// The input isn't JSON of the expected shape. The error was found at
+    /// the given 1-based line and column
+    InvalidJson { line: usize, column: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength { found, expected } => {
+                write!(f, "expected {expected} cells but found {found}")
+            }
+            Self::InvalidChar { pos, ch } => {
+                write!(f, "invalid character {ch:?} at position {pos}")
+            }
+            Self::OutOfRange { pos, value } => {
+                write!(f, "value {value} at position {pos} is out of range")
+            }
+            Self::InvalidJson { line, column } => {
+                write!(f, "invalid puzzle JSON at line {line}, column {column}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Filled cells whose values can't all be correct. Coordinates are
+/// zero-indexed `(row, col)` pairs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+    /// Two cells in the same row, column or subsection hold the same value
+    Duplicate { first: (usize, usize), second: (usize, usize), value: u8 },
+
+    /// A cell shared between two boards holds a different value in each
+    Mismatch { cell: (usize, usize), ours: u8, theirs: u8 },
+
+    /// The blanks of a unit can't hold all of its missing values, even
+    /// though no value is repeated. Units are numbered from zero as in
+    /// [`Sudoku::remaining_in_unit`](crate::Sudoku::remaining_in_unit)
+    Unsatisfiable { kind: UnitKind, index: usize },
+
+    /// A cell holds a value its parity constraint doesn't allow
+    WrongParity { cell: (usize, usize), value: u8, parity: Parity },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Duplicate { first: (r1, c1), second: (r2, c2), value } => {
+                write!(
+                    f,
+                    "value {value} appears at both R{}C{} and R{}C{}",
+                    r1 + 1,
+                    c1 + 1,
+                    r2 + 1,
+                    c2 + 1
+                )
+            }
+            Self::Mismatch { cell: (row, col), ours, theirs } => {
+                write!(
+                    f,
+                    "shared cell R{}C{} is {ours} on one board but {theirs} on \
+                     the other",
+                    row + 1,
+                    col + 1
+                )
+            }
+            Self::Unsatisfiable { kind, index } => {
+                let kind = match kind {
+                    UnitKind::Row => "row",
+                    UnitKind::Column => "column",
+                    UnitKind::Box => "box",
+                };
+
+                write!(
+                    f,
+                    "{kind} {} can't hold all of its missing values",
+                    index + 1
+                )
+            }
+            Self::WrongParity { cell: (row, col), value, parity } => {
+                let parity = match parity {
+                    Parity::Odd => "odd",
+                    Parity::Even => "even",
+                };
+
+                write!(
+                    f,
+                    "value {value} at R{}C{} isn't {parity}",
+                    row + 1,
+                    col + 1
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Conflict {}
+
+/// Reasons the solver can fail to produce a solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    /// Every branch of the search led to a contradiction
+    NoSolution,
+
+    /// The puzzle has more than one solution but a unique one was required
+    NotUnique,
+
+    /// The board already repeated a value in some unit, or held a value of
+    /// the wrong parity, before solving began
+    AlreadyInvalid,
+
+    /// Logical deduction stalled and guessing wasn't allowed
+    RequiresGuessing,
+
+    /// More guesses were needed than the configured limit allows
+    GuessLimitExceeded,
+
+    /// The caller asked for the solve to stop before it finished
+    Cancelled,
+
+    /// The search was still running when its time limit ran out
+    Timeout,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSolution => write!(f, "the puzzle has no solution"),
+            Self::NotUnique => {
+                write!(f, "the puzzle has more than one solution")
+            }
+            Self::AlreadyInvalid => {
+                write!(f, "the puzzle already breaks one of its constraints")
+            }
+            Self::RequiresGuessing => {
+                write!(f, "the puzzle can't be solved without guessing")
+            }
+            Self::GuessLimitExceeded => {
+                write!(f, "the solver exceeded its guess limit")
+            }
+            Self::Cancelled => write!(f, "the solve was cancelled"),
+            Self::Timeout => write!(f, "the solver timed out"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Any error produced by this crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Conflict(Conflict),
+    Solve(SolveError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "parse error: {e}"),
+            Self::Conflict(e) => write!(f, "invalid puzzle: {e}"),
+            Self::Solve(e) => write!(f, "solve failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse(e) => Some(e),
+            Self::Conflict(e) => Some(e),
+            Self::Solve(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<Conflict> for Error {
+    fn from(e: Conflict) -> Self {
+        Self::Conflict(e)
+    }
+}
+
+impl From<SolveError> for Error {
+    fn from(e: SolveError) -> Self {
+        Self::Solve(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Conflict, Error, ParseError, SolveError};
+    use crate::UnitKind;
+
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn error_is_send_and_sync() {
+        assert_send_sync::<Error>();
+    }
+
+    #[test]
+    fn messages_name_the_problem() {
+        let parse = Error::from(ParseError::InvalidChar { pos: 12, ch: 'x' });
+        assert_eq!(
+            parse.to_string(),
+            "parse error: invalid character 'x' at position 12"
+        );
+
+        let conflict = Error::from(Conflict::Duplicate {
+            first: (0, 1),
+            second: (4, 1),
+            value: 7,
+        });
+        assert_eq!(
+            conflict.to_string(),
+            "invalid puzzle: value 7 appears at both R1C2 and R5C2"
+        );
+
+        let unit = Conflict::Unsatisfiable { kind: UnitKind::Column, index: 2 };
+        assert_eq!(
+            unit.to_string(),
+            "column 3 can't hold all of its missing values"
+        );
+
+        let solve = Error::from(SolveError::RequiresGuessing);
+        assert_eq!(
+            solve.to_string(),
+            "solve failed: the puzzle can't be solved without guessing"
+        );
+        assert_eq!(SolveError::Timeout.to_string(), "the solver timed out");
+    }
+
+    #[test]
+    fn source_is_the_wrapped_error() {
+        let error = Error::from(SolveError::NoSolution);
+        let source = std::error::Error::source(&error).unwrap();
+
+        assert_eq!(source.to_string(), "the puzzle has no solution");
+    }
+}
@@ -0,0 +1,556 @@
+use std::{
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use crate::{BOARD_LEN, BOARD_SIZE, Cell, ParseError, Sudoku};
+
+impl Sudoku {
+    /// The board as a single line of 81 characters in row-major order, with
+    /// `.` for cells that aren't filled
+    #[must_use]
+    pub fn to_line(&self) -> String {
+        self.grid
+            .iter()
+            .map(|cell| match cell {
+                Cell::Fixed(n) | Cell::Collapsed(n) => char::from(b'0' + n),
+                Cell::Empty | Cell::Superposition(_) => '.',
+            })
+            .collect()
+    }
+
+    /// The board as a grid of characters indexed by `[row][col]`, with each
+    /// filled cell's digit and `blank` for the rest. No borders or colours
+    /// are added, for callers doing their own rendering
+    #[must_use]
+    pub fn as_str_grid(&self, blank: char) -> [[char; BOARD_LEN]; BOARD_LEN] {
+        let mut grid = [[blank; BOARD_LEN]; BOARD_LEN];
+
+        for (idx, cell) in self.grid.iter().enumerate() {
+            if let Some(n) = cell.value() {
+                let (row, col) = Self::idx_to_coord(idx);
+                grid[row][col] = char::from(b'0' + n);
+            }
+        }
+
+        grid
+    }
+
+    /// The board with each value drawn as `glyphs[value - 1]` and unfilled
+    /// cells as `blank`, one row per line with nothing in between, e.g. to
+    /// share a puzzle as emoji. Glyphs may be any string, not just a single
+    /// character
+    #[must_use]
+    pub fn to_glyphs(&self, glyphs: &[&str; BOARD_LEN], blank: &str) -> String {
+        self.as_str_grid(' ')
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&ch| {
+                        ch.to_digit(10)
+                            .map_or(blank, |n| glyphs[n as usize - 1])
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The board as an answer key in plain text: nine lines of nine
+    /// space-separated cells, with clues in brackets so they stand out from
+    /// the values filled in by solving, e.g. `[5] 3 [6] ...`. Unfilled cells
+    /// are shown as `.`
+    #[must_use]
+    pub fn solution_grid_string(&self) -> String {
+        (0..BOARD_LEN)
+            .map(|row| {
+                (0..BOARD_LEN)
+                    .map(|col| {
+                        match self.grid[Self::coord_to_idx((row, col))] {
+                            Cell::Fixed(n) => format!("[{n}]"),
+                            Cell::Collapsed(n) => n.to_string(),
+                            Cell::Empty | Cell::Superposition(_) => {
+                                ".".to_string()
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Reads one puzzle per line in the format accepted by
+    /// [`Sudoku::from_str`], skipping blank lines
+    ///
+    /// # Errors
+    ///
+    /// Fails if reading fails, or with [`io::ErrorKind::InvalidData`] wrapping
+    /// a [`ParseError`] if a line isn't a valid puzzle
+    pub fn from_reader(reader: impl BufRead) -> io::Result<Vec<Self>> {
+        let mut puzzles = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            puzzles.push(
+                line.parse().map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData, e)
+                })?,
+            );
+        }
+
+        Ok(puzzles)
+    }
+
+    /// Writes the board as a single line, as produced by [`Sudoku::to_line`]
+    ///
+    /// # Errors
+    ///
+    /// Fails if writing fails
+    pub fn to_writer(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "{}", self.to_line())
+    }
+}
+
+impl Sudoku {
+    /// Parses the common multi-line layout of nine rows of nine characters,
+    /// using the same characters as [`Sudoku::from_str`]. Blank lines and
+    /// lines starting with `#` or `//` are skipped, as is any other text
+    /// before the grid, such as a title. The borders `|`, `+` and `-` are
+    /// ignored, and so are row labels: a single character like `A` or `1`
+    /// before a row's nine cells. A header of column labels is skipped too,
+    /// though labels made of digits are only recognised as one when the rows
+    /// below are labelled. Anything after the ninth row is ignored
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Sudoku::from_str`] would on the nine rows joined together,
+    /// without their labels and borders
+    pub fn from_block_str(s: &str) -> Result<Self, ParseError> {
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| {
+                !(line.is_empty()
+                    || line.starts_with('#')
+                    || line.starts_with("//"))
+            })
+            .map(|line| (line, block_cells(line)))
+            // Lines of nothing but borders separate the bands
+            .filter(|(_, cells)| {
+                cells.as_ref().is_none_or(|(row, _)| !row.is_empty())
+            })
+            .skip_while(|(_, cells)| cells.is_none())
+            .peekable();
+
+        let first = lines.next();
+
+        let header = matches!(
+            (&first, lines.peek()),
+            (Some((_, Some((_, false)))), Some((_, Some((_, true)))))
+        );
+
+        first
+            .filter(|_| !header)
+            .into_iter()
+            .chain(lines)
+            .take(BOARD_LEN)
+            .map(|(line, cells)| {
+                cells.map_or_else(|| line.to_string(), |(row, _)| row)
+            })
+            .collect::<String>()
+            .parse()
+    }
+
+    /// Length of the encoding produced by [`Sudoku::to_bytes`]
+    pub const BYTES_LEN: usize = BOARD_SIZE.div_ceil(2);
+
+    /// Packs the puzzle's clues into [`Sudoku::BYTES_LEN`] bytes, two cells
+    /// per byte with the earlier cell in the high nibble. Each nibble is `0`
+    /// for an empty cell or the value of a clue. Only `Fixed` cells are
+    /// stored; anything filled in by the solver is written as empty
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.grid
+            .chunks(2)
+            .map(|pair| {
+                let nibble = |cell: Option<&Cell>| match cell {
+                    Some(Cell::Fixed(n)) => *n,
+                    _ => 0,
+                };
+
+                nibble(pair.first()) << 4 | nibble(pair.get(1))
+            })
+            .collect()
+    }
+
+    /// Reads a puzzle written by [`Sudoku::to_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::WrongLength`] if `bytes` isn't exactly
+    /// [`Sudoku::BYTES_LEN`] long and [`ParseError::OutOfRange`] if a cell's
+    /// nibble is greater than 9. The nibble of padding after the last cell
+    /// must be 0, and is reported as position 81 if it isn't
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        if bytes.len() != Self::BYTES_LEN {
+            return Err(ParseError::WrongLength {
+                found: bytes.len(),
+                expected: Self::BYTES_LEN,
+            });
+        }
+
+        let padding = bytes[Self::BYTES_LEN - 1] & 0xF;
+
+        if padding != 0 {
+            return Err(ParseError::OutOfRange {
+                pos: BOARD_SIZE,
+                value: padding,
+            });
+        }
+
+        let mut sudoku = Self::default();
+
+        for (pos, cell) in sudoku.grid.iter_mut().enumerate() {
+            let byte = bytes[pos / 2];
+            let value = if pos % 2 == 0 { byte >> 4 } else { byte & 0xF };
+
+            *cell = match value {
+                0 => Cell::Empty,
+                1..=9 => Cell::Fixed(value),
+                _ => return Err(ParseError::OutOfRange { pos, value }),
+            };
+        }
+
+        Ok(sudoku)
+    }
+
+    /// Builds a puzzle from 81 values in row-major order, where 0 is an empty
+    /// cell. This is [`Sudoku::from_zero_grid`] for data that isn't already
+    /// split into rows
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::WrongLength`] if `values` doesn't hold exactly
+    /// 81 values and [`ParseError::OutOfRange`] if one is greater than 9
+    pub fn from_flat(values: &[u8]) -> Result<Self, ParseError> {
+        if values.len() != BOARD_SIZE {
+            return Err(ParseError::WrongLength {
+                found: values.len(),
+                expected: BOARD_SIZE,
+            });
+        }
+
+        let mut sudoku = Self::default();
+
+        for (pos, (cell, &value)) in
+            sudoku.grid.iter_mut().zip(values).enumerate()
+        {
+            *cell = match value {
+                0 => Cell::Empty,
+                1..=9 => Cell::Fixed(value),
+                _ => return Err(ParseError::OutOfRange { pos, value }),
+            };
+        }
+
+        Ok(sudoku)
+    }
+}
+
+/// The cells in a line of a block grid, without whitespace, borders or a row
+/// label, and whether there was a label. `None` if the line holds anything
+/// else, like a title or a header of lettered column labels
+fn block_cells(line: &str) -> Option<(String, bool)> {
+    let tokens: Vec<&str> = line
+        .split(|ch: char| ch.is_whitespace() || matches!(ch, '|' | '+' | '-'))
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    let cells = |tokens: &[&str]| {
+        let cells = tokens.concat();
+        cells.chars().all(|ch| matches!(ch, '0'..='9' | '.')).then_some(cells)
+    };
+
+    if let Some((label, rest)) = tokens.split_first()
+        && label.chars().count() == 1
+        && let Some(row) = cells(rest).filter(|row| row.len() == BOARD_LEN)
+    {
+        return Some((row, true));
+    }
+
+    cells(&tokens).map(|row| (row, false))
+}
+
+impl FromStr for Sudoku {
+    type Err = ParseError;
+
+    /// Parses a puzzle from 81 characters in row-major order, where `1`-`9`
+    /// are clues and `0` or `.` are empty cells. All whitespace is ignored,
+    /// so rows may be separated by spaces or newlines. Positions in errors
+    /// count only the characters that aren't whitespace
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cells = || s.chars().filter(|ch| !ch.is_whitespace());
+
+        let found = cells().count();
+        if found != BOARD_SIZE {
+            return Err(ParseError::WrongLength {
+                found,
+                expected: BOARD_SIZE,
+            });
+        }
+
+        let mut sudoku = Self::default();
+
+        for (pos, ch) in cells().enumerate() {
+            sudoku.grid[pos] = match ch {
+                '0' | '.' => Cell::Empty,
+                '1'..='9' => Cell::Fixed(ch as u8 - b'0'),
+                _ => return Err(ParseError::InvalidChar { pos, ch }),
+            };
+        }
+
+        Ok(sudoku)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fmt::Write,
+        io::{self, Cursor},
+    };
+
+    use crate::{ParseError, Sudoku};
+
+    const EASY: &str = "000260701680070090190004500820100040004602900050003028009300074040050036703018000";
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    fn easy_rows() -> impl Iterator<Item = &'static str> {
+        (0..9).map(|row| &EASY[row * 9..row * 9 + 9])
+    }
+
+    fn assert_reads_easy(text: &str) {
+        assert!(
+            Sudoku::from_block_str(text)
+                .unwrap()
+                .values_eq(&EASY.parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn block_skips_a_comment_header() {
+        let rows = easy_rows().collect::<Vec<_>>().join("\n");
+        let text = format!("# Easy\n// From a forum\n\n{rows}\n");
+
+        assert!(text.ends_with('\n'));
+        assert_reads_easy(&text);
+    }
+
+    #[test]
+    fn block_skips_a_title_and_labels() {
+        let border = "  +-------+-------+-------+";
+        let mut text =
+            format!("Puzzle 12, easy\n    1 2 3   4 5 6   7 8 9\n{border}\n");
+
+        for (label, row) in ('A'..='I').zip(easy_rows()) {
+            let cells: Vec<String> =
+                row.chars().map(|ch| ch.to_string()).collect();
+
+            writeln!(
+                text,
+                "{label} | {} | {} | {} |",
+                cells[..3].join(" "),
+                cells[3..6].join(" "),
+                cells[6..].join(" ")
+            )
+            .unwrap();
+
+            if matches!(label, 'C' | 'F' | 'I') {
+                writeln!(text, "{border}").unwrap();
+            }
+        }
+
+        assert_reads_easy(&text);
+
+        // Digit labels on the rows, under a lettered header
+        let mut text = String::from("  A B C D E F G H I\n");
+
+        for (label, row) in (1..=9).zip(easy_rows()) {
+            writeln!(text, "{label} {row}").unwrap();
+        }
+
+        assert_reads_easy(&text);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let (full, empty) = ("9".repeat(81), "0".repeat(81));
+
+        for puzzle in [EASY, HARD, &full, &empty] {
+            let board: Sudoku = puzzle.parse().unwrap();
+            let bytes = board.to_bytes();
+
+            assert_eq!(bytes.len(), Sudoku::BYTES_LEN);
+            assert_eq!(Sudoku::from_bytes(&bytes).unwrap().to_bytes(), bytes);
+            assert!(Sudoku::from_bytes(&bytes).unwrap().values_eq(&board));
+        }
+    }
+
+    #[test]
+    fn bytes_reject_set_padding() {
+        let board: Sudoku = HARD.parse().unwrap();
+        let mut bytes = board.to_bytes();
+        *bytes.last_mut().unwrap() |= 0x3;
+
+        assert_eq!(
+            Sudoku::from_bytes(&bytes).map(|board| board.grid),
+            Err(ParseError::OutOfRange { pos: 81, value: 3 })
+        );
+    }
+
+    #[test]
+    fn lines_round_trip_through_a_reader_and_writer() {
+        let puzzles: Vec<Sudoku> =
+            [EASY, HARD].iter().map(|p| p.parse().unwrap()).collect();
+
+        let mut written = Vec::new();
+
+        for puzzle in &puzzles {
+            puzzle.to_writer(&mut written).unwrap();
+            written.push(b'\n');
+        }
+
+        let read = Sudoku::from_reader(Cursor::new(written)).unwrap();
+
+        assert_eq!(read.len(), puzzles.len());
+        assert!(read.iter().zip(&puzzles).all(|(a, b)| a.grid == b.grid));
+
+        let error = Sudoku::from_reader(Cursor::new(format!("{EASY}\n123\n")))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn parsing_rejects_the_wrong_length() {
+        assert_eq!(
+            EASY[1..].parse::<Sudoku>().map(|board| board.grid),
+            Err(ParseError::WrongLength { found: 80, expected: 81 })
+        );
+        assert_eq!(
+            format!("{EASY}0").parse::<Sudoku>().map(|board| board.grid),
+            Err(ParseError::WrongLength { found: 82, expected: 81 })
+        );
+
+        // Whitespace doesn't count towards the length
+        let spaced = easy_rows().collect::<Vec<_>>().join("\n ");
+        assert!(spaced.parse::<Sudoku>().is_ok());
+    }
+
+    #[test]
+    fn parsing_rejects_an_invalid_character() {
+        let mut puzzle = HARD.to_string();
+        puzzle.replace_range(40..41, "x");
+
+        assert_eq!(
+            puzzle.parse::<Sudoku>().map(|board| board.grid),
+            Err(ParseError::InvalidChar { pos: 40, ch: 'x' })
+        );
+
+        // Positions skip whitespace
+        let spaced = format!("  {puzzle}");
+        assert_eq!(
+            spaced.parse::<Sudoku>().map(|board| board.grid),
+            Err(ParseError::InvalidChar { pos: 40, ch: 'x' })
+        );
+    }
+
+    #[test]
+    fn flat_values_read_like_a_zero_grid() {
+        let values: Vec<u8> = HARD.bytes().map(|b| b - b'0').collect();
+
+        let mut rows = [[0; 9]; 9];
+        for (row, chunk) in rows.iter_mut().zip(values.chunks(9)) {
+            row.copy_from_slice(chunk);
+        }
+
+        let flat = Sudoku::from_flat(&values).unwrap();
+        assert_eq!(flat.grid, Sudoku::from_zero_grid(&rows).grid);
+        assert_eq!(flat.grid, HARD.parse::<Sudoku>().unwrap().grid);
+
+        assert_eq!(
+            Sudoku::from_flat(&values[1..]).map(|board| board.grid),
+            Err(ParseError::WrongLength { found: 80, expected: 81 })
+        );
+    }
+
+    #[test]
+    fn str_grid_of_a_solved_board_is_its_values() {
+        let solved = HARD.parse::<Sudoku>().unwrap().solve_into().unwrap();
+        let grid = solved.as_str_grid('.');
+
+        assert!(grid.iter().flatten().all(|&ch| ch != '.'));
+
+        let line: String = grid.iter().flatten().collect();
+        assert_eq!(line, solved.to_line());
+
+        // Unfilled cells use the blank given
+        let puzzle: Sudoku = HARD.parse().unwrap();
+        let line: String = puzzle.as_str_grid('0').iter().flatten().collect();
+        assert_eq!(line, HARD);
+    }
+
+    #[test]
+    fn answer_key_brackets_exactly_the_clues() {
+        let solved = HARD.parse::<Sudoku>().unwrap().solve_into().unwrap();
+        let key = solved.solution_grid_string();
+
+        let cells: Vec<&str> = key.split_whitespace().collect();
+        assert_eq!(key.lines().count(), 9);
+        assert_eq!(cells.len(), 81);
+
+        for ((cell, clue), value) in
+            cells.iter().zip(HARD.chars()).zip(solved.to_line().chars())
+        {
+            if clue == '0' {
+                assert_eq!(*cell, value.to_string());
+            } else {
+                assert_eq!(*cell, format!("[{clue}]"));
+            }
+        }
+
+        assert!(key.starts_with("1 [2] 6 4 3 7 9 5 8\n"));
+    }
+
+    #[test]
+    fn glyphs_replace_each_value_in_place() {
+        const EMOJI: [&str; 9] =
+            ["🍎", "🍌", "🍒", "🍇", "🍋", "🍊", "🍐", "🍑", "🍓"];
+
+        let puzzle: Sudoku = HARD.parse().unwrap();
+        let glyphs = puzzle.to_glyphs(&EMOJI, "⬜");
+
+        let rows: Vec<&str> = glyphs.lines().collect();
+        assert_eq!(rows.len(), 9);
+
+        for (row, line) in rows.iter().zip(HARD.as_bytes().chunks(9)) {
+            let expected: String = line
+                .iter()
+                .map(|&b| match b {
+                    b'0' => "⬜",
+                    _ => EMOJI[usize::from(b - b'1')],
+                })
+                .collect();
+
+            assert_eq!(*row, expected);
+        }
+
+        assert!(rows[0].starts_with("⬜🍌⬜"));
+    }
+}
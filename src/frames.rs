@@ -0,0 +1,229 @@
+use crate::{BOARD_LEN, BOARD_SIZE, Cell, Search, Sudoku, Technique};
+
+/// Work the search still has to do, innermost last
+enum Pending {
+    /// A board partway through deduction, with the number of passes in a row
+    /// that made no progress
+    Deduce { board: Sudoku, stalled: usize },
+
+    /// The values still to be guessed at `idx` on `board`. Only the first
+    /// `len` values are used, and the ones before `next` have been tried
+    Guess {
+        board: Sudoku,
+        idx: usize,
+        values: [u8; BOARD_LEN],
+        len: usize,
+        next: usize,
+    },
+}
+
+/// The iterator returned by [`Sudoku::solve_frames`]. Nothing is searched up
+/// front; each call to `next` runs the search only until the board changes
+pub struct SolveFrames {
+    ctx: Search<'static>,
+    order: [usize; BOARD_SIZE],
+    stack: Vec<Pending>,
+
+    /// The initialized puzzle, until it has been yielded
+    start: Option<Sudoku>,
+
+    /// The last frame yielded, so repeated states can be skipped
+    last: Option<Sudoku>,
+}
+
+impl SolveFrames {
+    fn new(mut board: Sudoku) -> Self {
+        board.initialize_superpositions();
+
+        let ctx = Search::default();
+        let order = ctx.config.scan_order();
+
+        let mut deduced = board;
+        deduced.propagate_all();
+
+        Self {
+            ctx,
+            order,
+            stack: vec![Pending::Deduce { board: deduced, stalled: 0 }],
+            start: Some(board),
+            last: None,
+        }
+    }
+
+    /// Runs one deterministic pass on `board`, or decides what to do with it
+    /// once deduction has stalled. Returns the state to show, if any
+    fn deduce(&mut self, mut board: Sudoku, stalled: usize) -> Option<Sudoku> {
+        if !board.is_solved() && stalled <= 3 {
+            let Some(progress) = board.deduce_pass(&mut self.ctx, &self.order)
+            else {
+                return self.backtrack();
+            };
+
+            let stalled = if progress { 0 } else { stalled + 1 };
+            self.stack.push(Pending::Deduce { board, stalled });
+            return Some(board);
+        }
+
+        if board.is_complete_and_correct() {
+            // Only the first solution is shown
+            self.stack.clear();
+            return Some(board);
+        }
+
+        if board.is_solved() {
+            return self.backtrack();
+        }
+
+        let (idx, values, len) = board.guesses(&mut self.ctx);
+        self.stack.push(Pending::Guess { board, idx, values, len, next: 0 });
+        None
+    }
+
+    /// Places the next value to be guessed at `idx` that forward checking
+    /// doesn't rule out, returning the board with the guess made
+    fn guess(
+        &mut self,
+        board: &Sudoku,
+        idx: usize,
+        values: [u8; BOARD_LEN],
+        len: usize,
+        mut next: usize,
+    ) -> Option<Sudoku> {
+        while next < len {
+            let mut guessed = *board;
+            guessed.grid[idx] = Cell::Collapsed(values[next]);
+            next += 1;
+
+            if self.ctx.config.forward_checking
+                && !guessed.survives_placement(idx)
+            {
+                continue;
+            }
+
+            self.ctx.record(Technique::Guess);
+            self.stack.push(Pending::Guess {
+                board: *board,
+                idx,
+                values,
+                len,
+                next,
+            });
+
+            let mut deduced = guessed;
+            deduced.propagate_all();
+            self.stack.push(Pending::Deduce { board: deduced, stalled: 0 });
+
+            return Some(guessed);
+        }
+
+        self.backtrack()
+    }
+
+    /// The board from before the innermost guess still being tried, which
+    /// the search returns to after a dead end
+    fn backtrack(&self) -> Option<Sudoku> {
+        self.stack.last().map(|pending| match *pending {
+            Pending::Deduce { board, .. } | Pending::Guess { board, .. } => {
+                board
+            }
+        })
+    }
+}
+
+impl Iterator for SolveFrames {
+    type Item = Sudoku;
+
+    fn next(&mut self) -> Option<Sudoku> {
+        if let Some(start) = self.start.take() {
+            self.last = Some(start);
+            return Some(start);
+        }
+
+        while let Some(pending) = self.stack.pop() {
+            let frame = match pending {
+                Pending::Deduce { board, stalled } => {
+                    self.deduce(board, stalled)
+                }
+                Pending::Guess { board, idx, values, len, next } => {
+                    self.guess(&board, idx, values, len, next)
+                }
+            };
+
+            // Consecutive identical states are skipped
+            if let Some(frame) = frame
+                && self.last.is_none_or(|last| last.grid != frame.grid)
+            {
+                self.last = Some(frame);
+                return Some(frame);
+            }
+        }
+
+        None
+    }
+}
+
+impl Sudoku {
+    /// Solves the board like [`Sudoku::solve`], yielding a snapshot of the
+    /// board after every deterministic pass, guess and backtrack. The first
+    /// frame is the initialized puzzle and, if a solution exists, the last is
+    /// the solution. Consecutive identical states are skipped. The search
+    /// runs lazily, so stopping early skips the rest of the work
+    pub fn solve_frames(self) -> impl Iterator<Item = Self> {
+        SolveFrames::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SolveFrames;
+    use crate::{Cell, Sudoku};
+
+    // Arto Inkala's puzzle, which needs several guesses
+    const INKALA: &str = "800000000003600000070090200050007000000045700000100030001000068008500010090000400";
+
+    fn filled(board: &Sudoku) -> usize {
+        board.grid.iter().filter_map(Cell::value).count()
+    }
+
+    #[test]
+    fn frames_run_from_the_puzzle_to_the_solution() {
+        let puzzle: Sudoku = INKALA.parse().unwrap();
+
+        let mut initialized = puzzle;
+        initialized.initialize_superpositions();
+
+        let mut solution = initialized;
+        solution.solve().unwrap();
+
+        let frames: Vec<Sudoku> = puzzle.solve_frames().collect();
+
+        assert_eq!(frames.first().unwrap().grid, initialized.grid);
+        assert_eq!(frames.last().unwrap().grid, solution.grid);
+
+        let mut backtracks = 0;
+
+        for (i, pair) in frames.windows(2).enumerate() {
+            assert_ne!(pair[0].grid, pair[1].grid);
+
+            // Cells are only emptied by returning to a board from before a
+            // guess, which has been shown already
+            if filled(&pair[1]) < filled(&pair[0]) {
+                assert!(frames[..=i].iter().any(|f| f.grid == pair[1].grid));
+                backtracks += 1;
+            }
+        }
+
+        assert!(backtracks > 0);
+    }
+
+    #[test]
+    fn frames_are_searched_lazily() {
+        let mut frames = SolveFrames::new(INKALA.parse().unwrap());
+
+        frames.next().unwrap();
+        assert_eq!(frames.ctx.passes, 0);
+
+        frames.next().unwrap();
+        assert_eq!(frames.ctx.passes, 1);
+    }
+}
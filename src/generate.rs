@@ -0,0 +1,160 @@
+use rand::{Rng, seq::SliceRandom};
+
+use crate::{BOARD_SIZE, Cell, Difficulty, Search, Sudoku};
+
+/// How many puzzles [`Sudoku::generate_with_difficulty`] tries before settling
+/// for the closest match
+const MAX_DIFFICULTY_ATTEMPTS: usize = 100;
+
+impl Sudoku {
+    /// Generates a random puzzle with a unique solution. Clues are removed
+    /// from a random solved board for as long as the solution stays unique
+    pub fn generate<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::reduce_random_solution(rng, |_| true)
+    }
+
+    /// Generates a random puzzle with a unique solution that
+    /// [rates](Sudoku::rate) as `target`. Clues are only removed while the
+    /// puzzle stays at or below the target, so easier targets are reached
+    /// quickly. If no attempt hits the target exactly, the closest puzzle
+    /// found is returned
+    pub fn generate_with_difficulty<R: Rng + ?Sized>(
+        target: Difficulty,
+        rng: &mut R,
+    ) -> Self {
+        let mut closest: Option<(Self, Difficulty)> = None;
+
+        for _ in 0..MAX_DIFFICULTY_ATTEMPTS {
+            let puzzle = Self::reduce_random_solution(rng, |puzzle| {
+                puzzle.rate().is_some_and(|rating| rating <= target)
+            });
+
+            let Some(rating) = puzzle.rate() else { continue };
+
+            if rating == target {
+                return puzzle;
+            }
+
+            if closest.is_none_or(|(_, best)| rating > best) {
+                closest = Some((puzzle, rating));
+            }
+        }
+
+        closest.map_or_else(|| Self::generate(rng), |(puzzle, _)| puzzle)
+    }
+
+    /// Removes clues from a random solved board in random order, keeping each
+    /// removal only if the solution stays unique and `keep` accepts the result
+    fn reduce_random_solution<R: Rng + ?Sized>(
+        rng: &mut R,
+        keep: impl Fn(&Self) -> bool,
+    ) -> Self {
+        let mut puzzle = Self::random_solution(rng);
+
+        let mut order: Vec<usize> = (0..BOARD_SIZE).collect();
+        order.shuffle(rng);
+
+        for idx in order {
+            let clue = puzzle.grid[idx];
+            puzzle.grid[idx] = Cell::Empty;
+
+            if puzzle.count_solutions(2) != 1 || !keep(&puzzle) {
+                puzzle.grid[idx] = clue;
+            }
+        }
+
+        puzzle
+    }
+
+    /// Completes the board to a random valid solution while keeping every
+    /// filled cell. Unlike [`Sudoku::solve`], the guess order at each cell is
+    /// shuffled, so repeated calls produce different solutions. Returns
+    /// whether a completion exists; the board is left untouched if not
+    pub fn fill_random_solution<R: Rng + ?Sized>(
+        &mut self,
+        mut rng: &mut R,
+    ) -> bool {
+        let mut board = *self;
+        board.initialize_superpositions();
+
+        let mut ctx = Search { rng: Some(&mut rng), ..Search::default() };
+
+        if board.search_first(&mut ctx).is_err() {
+            return false;
+        }
+
+        *self = board;
+        true
+    }
+
+    /// A random solved board where every cell is `Fixed`
+    fn random_solution<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut solution = Self::default();
+        assert!(solution.fill_random_solution(rng));
+
+        for cell in &mut solution.grid {
+            if let Cell::Collapsed(n) = *cell {
+                *cell = Cell::Fixed(n);
+            }
+        }
+
+        solution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::{Cell, Difficulty, SolutionClass, Sudoku};
+
+    #[test]
+    fn random_solution_keeps_every_clue() {
+        // The top two rows of a puzzle, leaving many completions
+        let puzzle: Sudoku =
+            format!("020000000000600003{}", "0".repeat(63)).parse().unwrap();
+
+        let mut solutions = Vec::new();
+
+        for seed in 0..4 {
+            let mut board = puzzle;
+            assert!(
+                board.fill_random_solution(&mut StdRng::seed_from_u64(seed))
+            );
+
+            assert!(board.is_complete_and_correct());
+            assert!(puzzle.grid.iter().zip(&board.grid).all(|(clue, cell)| {
+                !matches!(clue, Cell::Fixed(_)) || clue == cell
+            }));
+
+            solutions.push(board.to_line());
+        }
+
+        solutions.dedup();
+        assert!(solutions.len() > 1);
+    }
+
+    #[test]
+    fn random_solution_leaves_unsolvable_boards_alone() {
+        let puzzle: Sudoku =
+            format!("12345678.{}9{}", ".".repeat(44), ".".repeat(27))
+                .parse()
+                .unwrap();
+
+        let mut board = puzzle;
+        assert!(!board.fill_random_solution(&mut StdRng::seed_from_u64(0)));
+        assert_eq!(board.grid, puzzle.grid);
+    }
+
+    #[test]
+    fn easy_target_generates_an_easy_puzzle() {
+        for seed in 0..3 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let puzzle =
+                Sudoku::generate_with_difficulty(Difficulty::Easy, &mut rng);
+
+            assert_eq!(puzzle.rate(), Some(Difficulty::Easy));
+            assert_eq!(puzzle.solution_class(), SolutionClass::Unique);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use std::{
+    iter,
+    sync::{LazyLock, Mutex},
+};
+
+use crate::{BOARD_LEN, BOARD_SIZE, Sudoku};
+
+/// The regions and extra groups of a variant, which stay the same for the
+/// whole solve. Layouts are interned, so every board with the same layout
+/// points at the same one and each distinct layout is only allocated once
+#[derive(Debug, PartialEq, Eq)]
+pub struct Layout {
+    /// For each cell, a bitset of the cells sharing one of its extra groups,
+    /// with bit `n` standing for the cell at flat index `n`
+    pub extra_peers: [u128; BOARD_SIZE],
+
+    /// The region (0-8) containing each cell. Regions play the part of
+    /// subsections everywhere, so this is the standard 3x3 layout unless a
+    /// jigsaw layout was given to [`Sudoku::with_region_map`]
+    pub regions: [u8; BOARD_SIZE],
+
+    /// The cells of each region in board order, kept in step with `regions`
+    /// so the cells of a region can be found without a scan
+    pub region_cells: [[u8; BOARD_LEN]; BOARD_LEN],
+
+    /// For each cell, a bitset like `extra_peers` of every cell it sees
+    /// through a row, column, region or extra group. A cell doesn't see
+    /// itself
+    pub peers: [u128; BOARD_SIZE],
+}
+
+/// Every layout handed out so far. Variants are set up once per puzzle, so
+/// this stays small
+static LAYOUTS: Mutex<Vec<&'static Layout>> = Mutex::new(Vec::new());
+
+static STANDARD: LazyLock<&'static Layout> = LazyLock::new(|| {
+    let regions = std::array::from_fn(|idx| {
+        let region = Sudoku::box_of(Sudoku::row_of(idx), Sudoku::col_of(idx));
+        u8::try_from(region).expect("Region out of range")
+    });
+
+    Layout::new(&[0; BOARD_SIZE], &regions)
+});
+
+impl Layout {
+    /// The interned layout with the given extra peers and regions
+    pub fn new(
+        extra_peers: &[u128; BOARD_SIZE],
+        regions: &[u8; BOARD_SIZE],
+    ) -> &'static Self {
+        let mut region_cells = [[0; BOARD_LEN]; BOARD_LEN];
+        let mut sizes = [0; BOARD_LEN];
+
+        for (idx, &region) in regions.iter().enumerate() {
+            let region = usize::from(region);
+            region_cells[region][sizes[region]] =
+                u8::try_from(idx).expect("Index out of range");
+            sizes[region] += 1;
+        }
+
+        let peers = std::array::from_fn(|idx| {
+            let (row, col) = Sudoku::idx_to_coord(idx);
+            let region = regions[idx];
+
+            let standard = (0..BOARD_SIZE)
+                .filter(|&other| {
+                    let (other_row, other_col) = Sudoku::idx_to_coord(other);
+
+                    other_row == row
+                        || other_col == col
+                        || regions[other] == region
+                })
+                .fold(0u128, |mask, other| mask | 1 << other);
+
+            (standard | extra_peers[idx]) & !(1 << idx)
+        });
+
+        let layout = Self {
+            extra_peers: *extra_peers,
+            regions: *regions,
+            region_cells,
+            peers,
+        };
+
+        let mut layouts =
+            LAYOUTS.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        if let Some(&existing) = layouts.iter().find(|&&l| *l == layout) {
+            return existing;
+        }
+
+        let layout = Box::leak(Box::new(layout));
+        layouts.push(layout);
+        layout
+    }
+
+    /// The standard 3x3 subsections with no extra groups
+    pub fn standard() -> &'static Self {
+        *STANDARD
+    }
+}
+
+impl Sudoku {
+    /// Adds groups of cells, given as flat indices, that must each hold
+    /// distinct values on top of the usual rows, columns and subsections.
+    /// This covers variants such as X-Sudoku, where both long diagonals are
+    /// extra groups. Values are only kept from repeating within a group; a
+    /// group of nine cells isn't required to hold every value
+    ///
+    /// # Panics
+    ///
+    /// Panics if a group has more than nine cells, names a cell off the board
+    /// or names the same cell twice
+    #[must_use]
+    pub fn with_extra_groups(mut self, groups: Vec<Vec<usize>>) -> Self {
+        let mut extra_peers = self.layout.extra_peers;
+
+        for group in groups {
+            assert!(group.len() <= BOARD_LEN, "Group has too many cells");
+
+            let mut members = 0u128;
+
+            for &idx in &group {
+                assert!(idx < BOARD_SIZE, "Index out of range");
+                assert!(members & 1 << idx == 0, "Cell repeated in group");
+                members |= 1 << idx;
+            }
+
+            for &idx in &group {
+                extra_peers[idx] |= members & !(1 << idx);
+            }
+        }
+
+        self.layout = Layout::new(&extra_peers, &self.layout.regions);
+        self
+    }
+
+    /// Replaces the 3x3 subsections with irregular regions for jigsaw
+    /// puzzles, where `regions[idx]` is the region (0-8) of the cell at flat
+    /// index `idx`. Every rule that applies to subsections applies to the
+    /// regions instead. The standard layout is [`Sudoku::region_map`] of a
+    /// new board, so passing it back changes nothing
+    ///
+    /// # Panics
+    ///
+    /// Panics if a region id is greater than 8 or a region doesn't have
+    /// exactly nine cells
+    #[must_use]
+    pub fn with_region_map(mut self, regions: [u8; BOARD_SIZE]) -> Self {
+        let mut sizes = [0; BOARD_LEN];
+
+        for &region in &regions {
+            assert!(usize::from(region) < BOARD_LEN, "Region out of range");
+            sizes[usize::from(region)] += 1;
+        }
+
+        assert!(
+            sizes.iter().all(|&size| size == BOARD_LEN),
+            "Region doesn't have nine cells"
+        );
+
+        self.layout = Layout::new(&self.layout.extra_peers, &regions);
+        self
+    }
+
+    /// The region (0-8) of every cell, indexed by flat index. See
+    /// [`Sudoku::with_region_map`]
+    #[must_use]
+    pub const fn region_map(&self) -> [u8; BOARD_SIZE] {
+        self.layout.regions
+    }
+
+    /// The region containing the cell at flat index `idx`
+    pub(crate) const fn region_of(&self, idx: usize) -> usize {
+        self.layout.regions[idx] as usize
+    }
+
+    /// The flat indices of the cells set in a bitset of cells
+    pub(crate) fn cells_in(mut mask: u128) -> impl Iterator<Item = usize> {
+        iter::from_fn(move || {
+            (mask != 0).then(|| {
+                let peer = mask.trailing_zeros() as usize;
+                mask &= mask - 1;
+                peer
+            })
+        })
+    }
+
+    /// Every cell that can't share a value with the cell at `idx`, through
+    /// either the standard units or an extra group, in board order
+    pub(crate) fn all_peers(&self, idx: usize) -> impl Iterator<Item = usize> {
+        Self::cells_in(self.layout.peers[idx])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BOARD_LEN, BOARD_SIZE, Sudoku};
+
+    const EASY: &str = "000260701680070090190004500820100040004602900050003028009300074040050036703018000";
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    // Arto Inkala's puzzle, which needs several guesses
+    const INKALA: &str = "800000000003600000070090200050007000000045700000100030001000068008500010090000400";
+
+    fn diagonals() -> Vec<Vec<usize>> {
+        vec![
+            (0..BOARD_LEN).map(|i| i * BOARD_LEN + i).collect(),
+            (0..BOARD_LEN).map(|i| i * BOARD_LEN + BOARD_LEN - 1 - i).collect(),
+        ]
+    }
+
+    fn repeats_within(board: &Sudoku, group: &[usize]) -> bool {
+        let values = group.iter().filter_map(|&idx| board.grid[idx].value());
+        let seen = values.fold(0u16, |seen, value| seen | 1 << value);
+
+        seen.count_ones() as usize != group.len()
+    }
+
+    #[test]
+    fn diagonal_groups_solve_x_sudoku() {
+        let mut plain = Sudoku::empty();
+        plain.initialize_superpositions();
+        plain.solve().unwrap();
+
+        assert!(diagonals().iter().any(|group| repeats_within(&plain, group)));
+
+        let mut board = Sudoku::empty().with_extra_groups(diagonals());
+        board.initialize_superpositions();
+        board.solve().unwrap();
+
+        assert!(board.is_complete_and_correct());
+
+        for group in diagonals() {
+            assert!(!repeats_within(&board, &group));
+        }
+    }
+
+    #[test]
+    fn standard_region_map_changes_nothing() {
+        for puzzle in [EASY, HARD, INKALA] {
+            let mut plain: Sudoku = puzzle.parse().unwrap();
+            plain.initialize_superpositions();
+
+            let mut mapped = plain.with_region_map(plain.region_map());
+
+            assert_eq!(plain.solve_with_stats(), mapped.solve_with_stats());
+            assert_eq!(plain.grid, mapped.grid);
+        }
+    }
+
+    #[test]
+    fn jigsaw_regions_replace_the_subsections() {
+        // R1C3 and R2C4 trade regions
+        let mut regions = Sudoku::empty().region_map();
+        regions.swap(2, BOARD_LEN + 3);
+        let jigsaw = |board: Sudoku| board.with_region_map(regions);
+
+        // R1C3 now shares a region with R2C5 rather than with R3C1
+        let apart = Sudoku::empty().with_clue(0, 2, 5).with_clue(2, 0, 5);
+        assert!(apart.validate().is_err());
+        assert!(jigsaw(apart).validate().is_ok());
+
+        let together = Sudoku::empty().with_clue(0, 2, 5).with_clue(1, 4, 5);
+        assert!(together.validate().is_ok());
+        assert!(jigsaw(together).validate().is_err());
+
+        let mut board = jigsaw(HARD.parse().unwrap());
+        board.initialize_superpositions();
+        board.solve().unwrap();
+
+        assert!(board.is_complete_and_correct());
+
+        for region in 0..BOARD_LEN {
+            let cells: Vec<usize> = (0..BOARD_SIZE)
+                .filter(|&idx| usize::from(regions[idx]) == region)
+                .collect();
+
+            assert!(!repeats_within(&board, &cells));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{BOARD_LEN, ParseError, SolveError, Sudoku};
+
+/// The `{"grid": [[...], ...]}` shape accepted by [`Sudoku::from_json`]
+#[derive(Deserialize)]
+struct JsonPuzzle {
+    grid: Vec<Vec<u8>>,
+}
+
+impl Sudoku {
+    /// Parses a puzzle posted as `{"grid": [[0, 2, 0, ...], ...]}`: nine rows
+    /// of nine values, where 0 is an empty cell
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::InvalidJson`] if the input isn't an object with
+    /// a `grid` of arrays of small integers, [`ParseError::WrongLength`] if
+    /// the grid or one of its rows doesn't have nine entries, and
+    /// [`ParseError::OutOfRange`] if a value is greater than 9
+    pub fn from_json(json: &str) -> Result<Self, ParseError> {
+        let puzzle: JsonPuzzle = serde_json::from_str(json).map_err(|e| {
+            ParseError::InvalidJson { line: e.line(), column: e.column() }
+        })?;
+
+        if puzzle.grid.len() != BOARD_LEN {
+            return Err(ParseError::WrongLength {
+                found: puzzle.grid.len(),
+                expected: BOARD_LEN,
+            });
+        }
+
+        if let Some(row) = puzzle.grid.iter().find(|row| row.len() != BOARD_LEN)
+        {
+            return Err(ParseError::WrongLength {
+                found: row.len(),
+                expected: BOARD_LEN,
+            });
+        }
+
+        Self::from_flat(&puzzle.grid.concat())
+    }
+
+    /// Solves the board like [`Sudoku::solve_steps`] and returns the steps as
+    /// a JSON array for replaying the solve elsewhere, e.g.
+    /// `[{"technique": "HiddenSingle", "row": 0, "col": 2, "value": 6,
+    /// "eliminated": [1, 5]}, ...]`. Techniques use their variant names,
+    /// coordinates are zero-indexed, `value` is `null` for steps that only
+    /// remove candidates and `eliminated` lists the removed values
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Sudoku::solve_steps`] would
+    pub fn solve_trace_json(&mut self) -> Result<String, SolveError> {
+        let steps: Value = self
+            .solve_steps()?
+            .iter()
+            .map(|step| {
+                let eliminated: Vec<u8> = (1..=9u8)
+                    .filter(|n| step.eliminated & 1 << (n - 1) != 0)
+                    .collect();
+
+                json!({
+                    "technique": format!("{:?}", step.technique),
+                    "row": step.row,
+                    "col": step.col,
+                    "value": step.value,
+                    "eliminated": eliminated,
+                })
+            })
+            .collect();
+
+        Ok(steps.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::{ParseError, Sudoku};
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    #[test]
+    fn trace_replays_to_the_solution() {
+        let mut puzzle: Sudoku = HARD.parse().unwrap();
+        puzzle.initialize_superpositions();
+
+        let mut solution = puzzle;
+        let steps = solution.solve_steps().unwrap();
+
+        let mut traced = puzzle;
+        let trace: Vec<Value> =
+            serde_json::from_str(&traced.solve_trace_json().unwrap()).unwrap();
+
+        assert_eq!(trace.len(), steps.len());
+        assert!(traced.values_eq(&solution));
+
+        // Placing every traced value on the puzzle fills in the solution
+        let mut replayed = puzzle;
+
+        for step in &trace {
+            if let Some(value) = step["value"].as_u64() {
+                replayed.set(
+                    usize::try_from(step["row"].as_u64().unwrap()).unwrap(),
+                    usize::try_from(step["col"].as_u64().unwrap()).unwrap(),
+                    u8::try_from(value).unwrap(),
+                );
+            }
+        }
+
+        assert!(replayed.values_eq(&solution));
+        assert!(replayed.is_complete_and_correct());
+    }
+
+    /// [`HARD`] as rows of a JSON array
+    fn hard_rows() -> Vec<String> {
+        HARD.as_bytes()
+            .chunks(9)
+            .map(|row| {
+                let values: Vec<String> =
+                    row.iter().map(|&b| char::from(b).to_string()).collect();
+                format!("[{}]", values.join(", "))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn json_grid_parses_like_a_line() {
+        let json = format!(r#"{{"grid": [{}]}}"#, hard_rows().join(", "));
+
+        let board = Sudoku::from_json(&json).unwrap();
+        let expected: Sudoku = HARD.parse().unwrap();
+        assert_eq!(board.grid, expected.grid);
+    }
+
+    #[test]
+    fn json_grid_with_a_short_row_is_rejected() {
+        let mut rows = hard_rows();
+        rows[4] = "[0, 8, 0, 0, 4, 0, 0, 1]".to_string();
+
+        let json = format!(r#"{{"grid": [{}]}}"#, rows.join(", "));
+
+        assert_eq!(
+            Sudoku::from_json(&json).unwrap_err(),
+            ParseError::WrongLength { found: 8, expected: 9 }
+        );
+    }
+}
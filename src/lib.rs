@@ -0,0 +1,1816 @@
+#![warn(clippy::pedantic, clippy::nursery)]
+
+use std::{
+    collections::HashMap,
+    ops::ControlFlow,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
+
+use rand::{Rng, seq::SliceRandom};
+
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+#[cfg(feature = "tokio")]
+mod async_solve;
+mod candidates;
+mod canonical;
+mod config;
+mod corpus;
+mod difficulty;
+mod display;
+mod edit;
+mod error;
+mod format;
+mod frames;
+mod generate;
+mod groups;
+#[cfg(feature = "serde")]
+mod json;
+mod meta;
+#[cfg(feature = "std")]
+mod metrics;
+mod parity;
+mod report;
+mod samurai;
+mod save;
+mod step;
+mod strategy;
+mod symmetry;
+mod technique;
+mod unit;
+
+pub use config::{SolverConfig, UniquenessMode};
+pub use corpus::CorpusResult;
+pub use difficulty::Difficulty;
+pub use display::{BorderStyle, ColorTheme, DisplayOptions, SudokuDisplay};
+pub use error::{Conflict, Error, ParseError, SolveError};
+use groups::Layout;
+pub use meta::PuzzleMeta;
+#[cfg(feature = "std")]
+pub use metrics::SolveMetrics;
+pub use parity::Parity;
+pub use report::SolveReport;
+pub use samurai::SamuraiSudoku;
+pub use step::Step;
+pub use strategy::Strategy;
+pub use symmetry::Symmetry;
+pub use technique::Technique;
+pub use unit::UnitKind;
+
+/// Width and height of a subsection
+pub const BOARD_SEP: usize = 3;
+/// Width and height of the board, and the number of distinct values
+pub const BOARD_LEN: usize = BOARD_SEP * BOARD_SEP;
+/// Number of cells on the board
+pub const BOARD_SIZE: usize = BOARD_LEN * BOARD_LEN;
+
+/// How many solutions a puzzle has, as far as uniqueness is concerned. See
+/// [`Sudoku::solution_class`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SolutionClass {
+    None,
+    Unique,
+    Multiple,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    #[default]
+    Empty,
+    Fixed(u8),
+    Collapsed(u8),
+    Superposition([bool; BOARD_LEN]),
+}
+
+impl Cell {
+    const fn value(&self) -> Option<u8> {
+        match self {
+            Self::Fixed(n) | Self::Collapsed(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn count_superstates(&self) -> Option<usize> {
+        match self {
+            Self::Superposition(s) => Some(s.iter().filter(|&&x| x).count()),
+            _ => None,
+        }
+    }
+
+    fn collapse(&self) -> Option<u8> {
+        match self {
+            Self::Superposition(s) => {
+                let mut count = 0;
+                let mut value = 0;
+
+                for (idx, val) in s.iter().enumerate() {
+                    if *val {
+                        count += 1;
+                        value = idx + 1;
+                    }
+                }
+
+                if count == 1 {
+                    Some(u8::try_from(value).expect("Value out of range"))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Sudoku {
+    grid: [Cell; BOARD_SIZE],
+
+    /// The cells constrained to odd values, as a bitset with bit `n`
+    /// standing for the cell at flat index `n`, for parity variants
+    odd_cells: u128,
+
+    /// The cells constrained to even values, as a bitset like `odd_cells`
+    even_cells: u128,
+
+    /// The regions and extra groups of the variant. These never change
+    /// during a solve, so boards share them rather than copying them on
+    /// every guess
+    layout: &'static Layout,
+}
+
+impl std::default::Default for Sudoku {
+    fn default() -> Self {
+        Self {
+            grid: [const { Cell::Empty }; BOARD_SIZE],
+            odd_cells: 0,
+            even_cells: 0,
+            layout: Layout::standard(),
+        }
+    }
+}
+
+/// The cells of the board in row-major order
+impl IntoIterator for Sudoku {
+    type Item = Cell;
+    type IntoIter = std::array::IntoIter<Cell, BOARD_SIZE>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.grid.into_iter()
+    }
+}
+
+/// The cells of the board in row-major order
+impl<'a> IntoIterator for &'a Sudoku {
+    type Item = &'a Cell;
+    type IntoIter = std::slice::Iter<'a, Cell>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Sudoku {
+    #[must_use]
+    pub fn from_zero_grid(grid: &[[u8; BOARD_LEN]; BOARD_LEN]) -> Self {
+        let mut sudoku = Self::default();
+
+        let mut idx = 0;
+
+        for row in grid {
+            for cell in row {
+                sudoku.grid[idx] =
+                    if *cell == 0 { Cell::Empty } else { Cell::Fixed(*cell) };
+
+                idx += 1;
+            }
+        }
+
+        sudoku
+    }
+
+    const fn coord_to_idx((row, col): (usize, usize)) -> usize {
+        row * BOARD_LEN + col
+    }
+
+    /// Like `coord_to_idx`, but panics rather than returning a meaningless
+    /// index for coordinates off the board
+    fn checked_coord_to_idx((row, col): (usize, usize)) -> usize {
+        assert!(row < BOARD_LEN && col < BOARD_LEN, "Coordinate out of range");
+        Self::coord_to_idx((row, col))
+    }
+
+    const fn idx_to_coord(idx: usize) -> (usize, usize) {
+        let row = idx / BOARD_LEN;
+        let col = idx % BOARD_LEN;
+        (row, col)
+    }
+
+    /// The flat index of the cell at `(row, col)`, or `None` if the
+    /// coordinate is off the board
+    #[must_use]
+    pub const fn try_coord_to_idx(row: usize, col: usize) -> Option<usize> {
+        if row < BOARD_LEN && col < BOARD_LEN {
+            Some(Self::coord_to_idx((row, col)))
+        } else {
+            None
+        }
+    }
+
+    /// The `(row, col)` of the cell at flat index `idx`, or `None` if the
+    /// index is off the board
+    #[must_use]
+    pub const fn try_idx_to_coord(idx: usize) -> Option<(usize, usize)> {
+        if idx < BOARD_SIZE { Some(Self::idx_to_coord(idx)) } else { None }
+    }
+
+    /// The row (0-8) containing the cell at flat index `idx`
+    #[must_use]
+    pub const fn row_of(idx: usize) -> usize {
+        idx / BOARD_LEN
+    }
+
+    /// The column (0-8) containing the cell at flat index `idx`
+    #[must_use]
+    pub const fn col_of(idx: usize) -> usize {
+        idx % BOARD_LEN
+    }
+
+    /// The subsection (0-8, numbered in row-major order) containing the cell
+    /// at `(row, col)`
+    #[must_use]
+    pub const fn box_of(row: usize, col: usize) -> usize {
+        row / BOARD_SEP * BOARD_SEP + col / BOARD_SEP
+    }
+
+    const fn row_idx(idx: usize) -> usize {
+        let row = idx / BOARD_LEN;
+        row * BOARD_LEN
+    }
+
+    const fn col_idx(idx: usize) -> usize {
+        idx % BOARD_LEN
+    }
+
+    /// Whether every cell is filled. The values aren't checked against each
+    /// other; see [`Sudoku::is_complete_and_correct`]
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        self.grid
+            .iter()
+            .all(|cell| matches!(cell, Cell::Fixed(_) | Cell::Collapsed(_)))
+    }
+
+    /// Whether every cell is filled and no value is repeated in any row,
+    /// column or subsection
+    #[must_use]
+    pub fn is_complete_and_correct(&self) -> bool {
+        self.is_solved() && self.validate().is_ok()
+    }
+
+    /// Whether every blank cell has been given a superposition, i.e. no
+    /// `Cell::Empty` remains. The solver makes no progress on `Empty` cells,
+    /// so [`Sudoku::initialize_superpositions`] must run before solving
+    #[must_use]
+    pub fn is_initialized(&self) -> bool {
+        !self.grid.iter().any(|cell| matches!(cell, Cell::Empty))
+    }
+
+    /// Whether the cell at `(row, col)` is one of the puzzle's clues rather
+    /// than empty or filled in by the solver
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinate is off the board
+    #[must_use]
+    pub fn is_given(&self, row: usize, col: usize) -> bool {
+        matches!(
+            self.grid[Self::checked_coord_to_idx((row, col))],
+            Cell::Fixed(_)
+        )
+    }
+
+    /// The cells of the board in row-major order
+    pub fn iter(&self) -> std::slice::Iter<'_, Cell> {
+        self.grid.iter()
+    }
+
+    /// The number of clues on the board
+    #[must_use]
+    pub fn clue_count(&self) -> usize {
+        self.grid.iter().filter(|cell| matches!(cell, Cell::Fixed(_))).count()
+    }
+
+    /// The fewest clues a standard puzzle with a unique solution can have
+    pub const MIN_UNIQUE_CLUES: usize = 17;
+
+    /// The number of clues if it is below [`Sudoku::MIN_UNIQUE_CLUES`], in
+    /// which case the puzzle can't have a unique solution. This is a cheap
+    /// check to run before [`Sudoku::count_solutions`]. Extra groups and
+    /// parity constraints aren't taken into account
+    #[must_use]
+    pub fn warn_if_too_few_clues(&self) -> Option<usize> {
+        let clues = self.clue_count();
+        (clues < Self::MIN_UNIQUE_CLUES).then_some(clues)
+    }
+
+    /// The `(row, col)` of every cell that isn't filled yet, in row-major
+    /// order
+    #[must_use]
+    pub fn empty_cells(&self) -> Vec<(usize, usize)> {
+        (0..BOARD_SIZE)
+            .filter(|&idx| self.grid[idx].value().is_none())
+            .map(Self::idx_to_coord)
+            .collect()
+    }
+
+    /// Whether both boards hold the same values in the same cells, treating
+    /// clues and solver placements alike. Candidate sets are ignored, so any
+    /// unfilled cell matches any other unfilled cell
+    #[must_use]
+    pub fn values_eq(&self, other: &Self) -> bool {
+        self.grid.iter().zip(&other.grid).all(|(a, b)| a.value() == b.value())
+    }
+
+    /// Every cell whose value differs between `self` and `other`, as
+    /// `(row, col, old, new)` where unfilled cells count as 0. Like
+    /// [`Sudoku::values_eq`], clues and solver placements are treated alike
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Vec<(usize, usize, u8, u8)> {
+        self.grid
+            .iter()
+            .zip(&other.grid)
+            .enumerate()
+            .filter_map(|(idx, (a, b))| {
+                let (old, new) =
+                    (a.value().unwrap_or(0), b.value().unwrap_or(0));
+                let (row, col) = Self::idx_to_coord(idx);
+
+                (old != new).then_some((row, col, old, new))
+            })
+            .collect()
+    }
+
+    /// Whether every clue of this puzzle is also a clue of `other`, in the
+    /// same cell and with the same value, so removing clues from `other`
+    /// could produce it. Only `Fixed` cells are compared
+    #[must_use]
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        self.grid.iter().zip(&other.grid).all(|(a, b)| match a {
+            Cell::Fixed(_) => a == b,
+            _ => true,
+        })
+    }
+
+    pub fn initialize_superpositions(&mut self) {
+        for idx in 0..BOARD_SIZE {
+            if matches!(self.grid[idx], Cell::Empty) {
+                self.grid[idx] = Cell::Superposition([true; BOARD_LEN]);
+                self.apply_parity(idx);
+            }
+        }
+    }
+
+    /// Removes the value of every filled cell from the candidates of its
+    /// peers, without placing anything
+    pub fn propagate_all(&mut self) {
+        for idx in 0..BOARD_SIZE {
+            self.propagate(idx);
+        }
+    }
+
+    /// Removes `value` from the candidates of the cell at `idx`, returning
+    /// whether it was a candidate
+    const fn eliminate(&mut self, idx: usize, value: u8) -> bool {
+        let Cell::Superposition(ref mut s) = self.grid[idx] else {
+            return false;
+        };
+
+        let was_candidate = s[value as usize - 1];
+        s[value as usize - 1] = false;
+        was_candidate
+    }
+
+    fn propagate(&mut self, idx: usize) {
+        self.apply_parity(idx);
+
+        let (Cell::Fixed(n) | Cell::Collapsed(n)) = self.grid[idx] else {
+            return;
+        };
+
+        // Nothing in the same row, column, region or extra group can be the
+        // same
+        for peer in Self::cells_in(self.layout.peers[idx]) {
+            if let &mut Cell::Superposition(ref mut s) = &mut self.grid[peer] {
+                s[n as usize - 1] = false;
+            }
+        }
+    }
+
+    fn solve_pure_negative(&mut self, idx: usize) -> bool {
+        // If no other cell in the same row/col/subsection can have a certain
+        // value, this cell must have that value
+
+        let Cell::Superposition(superposition) = self.grid[idx] else {
+            return false;
+        };
+
+        for (val_idx, _) in
+            superposition.iter().enumerate().filter(|(_, val)| **val)
+        {
+            let mut num_alternatives = 0;
+
+            // Nothing horizontally can be the same
+            let row_idx = Self::row_idx(idx);
+            for col in row_idx..row_idx + BOARD_LEN {
+                if let &mut Cell::Superposition(ref mut s) = &mut self.grid[col]
+                    && col != idx
+                    && s[val_idx]
+                {
+                    num_alternatives += 1;
+                }
+            }
+
+            if num_alternatives == 0 {
+                return self.place_hidden_single(idx, val_idx);
+            }
+
+            // Nothing vertically can be the same
+            let col_idx = Self::col_idx(idx);
+            num_alternatives = 0;
+            for row in (col_idx..BOARD_SIZE).step_by(BOARD_LEN) {
+                if let &mut Cell::Superposition(ref mut s) = &mut self.grid[row]
+                    && row != idx
+                    && s[val_idx]
+                {
+                    num_alternatives += 1;
+                }
+            }
+
+            if num_alternatives == 0 {
+                return self.place_hidden_single(idx, val_idx);
+            }
+
+            // Nothing in the same region can be the same
+            num_alternatives = 0;
+
+            for tmp_idx in self.unit_cells(UnitKind::Box, self.region_of(idx)) {
+                if let &mut Cell::Superposition(ref mut s) =
+                    &mut self.grid[tmp_idx]
+                    && tmp_idx != idx
+                    && s[val_idx]
+                {
+                    num_alternatives += 1;
+                }
+            }
+
+            if num_alternatives == 0 {
+                return self.place_hidden_single(idx, val_idx);
+            }
+        }
+
+        false
+    }
+
+    /// Collapses the cell at `idx` to the value at `val_idx`, unless a peer
+    /// already holds it. That only happens when the candidates are stale,
+    /// i.e. a filled cell hasn't been propagated yet, and placing the value
+    /// would create a duplicate. The stale candidate is removed instead and
+    /// nothing is placed
+    fn place_hidden_single(&mut self, idx: usize, val_idx: usize) -> bool {
+        let value = u8::try_from(val_idx + 1).expect("Value out of range");
+
+        if self
+            .all_peers(idx)
+            .any(|peer| self.grid[peer].value() == Some(value))
+        {
+            self.eliminate(idx, value);
+            return false;
+        }
+
+        self.grid[idx] = Cell::Collapsed(value);
+        true
+    }
+
+    /// Checks that no value appears twice in any row, column, subsection or
+    /// extra group, and that every filled cell meets its parity constraint
+    ///
+    /// # Errors
+    ///
+    /// Returns the first pair of conflicting cells found, or the first cell
+    /// of the wrong parity
+    pub fn validate(&self) -> Result<(), Conflict> {
+        for idx in 0..BOARD_SIZE {
+            let (Cell::Fixed(n) | Cell::Collapsed(n)) = self.grid[idx] else {
+                continue;
+            };
+
+            if let Some(parity) = self.parity_of(idx)
+                && !parity.allows(n)
+            {
+                return Err(Conflict::WrongParity {
+                    cell: Self::idx_to_coord(idx),
+                    value: n,
+                    parity,
+                });
+            }
+
+            if let Some(peer) = self.all_peers(idx).find(|&peer| {
+                matches!(
+                    self.grid[peer],
+                    Cell::Fixed(m) | Cell::Collapsed(m) if m == n
+                )
+            }) {
+                return Err(Conflict::Duplicate {
+                    first: Self::idx_to_coord(idx),
+                    second: Self::idx_to_coord(peer),
+                    value: n,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn peers(&self, idx: usize) -> impl Iterator<Item = usize> + use<> {
+        let row_idx = Self::row_idx(idx);
+        let col_idx = Self::col_idx(idx);
+
+        (row_idx..row_idx + BOARD_LEN)
+            .chain((col_idx..BOARD_SIZE).step_by(BOARD_LEN))
+            .chain(self.unit_cells(UnitKind::Box, self.region_of(idx)))
+            .filter(move |&peer| peer != idx)
+    }
+
+    /// Whether the cells at flat indices `a` and `b` share a row, column,
+    /// region or extra group. A cell doesn't see itself
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is off the board
+    #[must_use]
+    pub const fn sees(&self, a: usize, b: usize) -> bool {
+        assert!(a < BOARD_SIZE && b < BOARD_SIZE, "Index out of range");
+        let shares_unit = Self::row_of(a) == Self::row_of(b)
+            || Self::col_of(a) == Self::col_of(b)
+            || self.layout.regions[a] == self.layout.regions[b];
+
+        (a != b && shares_unit) || self.layout.extra_peers[a] & (1 << b) != 0
+    }
+
+    /// The flat indices of every cell that sees both `a` and `b`, in board
+    /// order. Neither `a` nor `b` is included, so this is where a wing or
+    /// chain ending in those two cells can eliminate candidates
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is off the board
+    #[must_use]
+    pub fn common_peers(&self, a: usize, b: usize) -> Vec<usize> {
+        assert!(a < BOARD_SIZE && b < BOARD_SIZE, "Index out of range");
+
+        (0..BOARD_SIZE)
+            .filter(|&idx| {
+                idx != a && idx != b && self.sees(idx, a) && self.sees(idx, b)
+            })
+            .collect()
+    }
+
+    /// Runs the deterministic phase of the solver, returning `false` if a
+    /// contradiction was found
+    fn deduce(&mut self, ctx: &mut Search<'_>) -> bool {
+        let mut iters_without_collapse = 0;
+
+        // Hidden singles are only sound once every filled cell has been
+        // removed from its peers' candidates
+        self.propagate_all();
+
+        let order = ctx.config.scan_order();
+
+        // An interrupted search stops at the next guess
+        while !self.is_solved() && ctx.interruption().is_none() {
+            let Some(progress) = self.deduce_pass(ctx, &order) else {
+                return false;
+            };
+
+            if progress {
+                iters_without_collapse = 0;
+            } else {
+                iters_without_collapse += 1;
+            }
+
+            if iters_without_collapse > 3 {
+                break;
+            }
+        }
+
+        true
+    }
+
+    /// Makes a single deterministic pass over the cells in `order`, returning
+    /// whether it made any progress, or `None` if a contradiction was found
+    fn deduce_pass(
+        &mut self,
+        ctx: &mut Search<'_>,
+        order: &[usize; BOARD_SIZE],
+    ) -> Option<bool> {
+        for &idx in order {
+            let before = self.grid[idx];
+
+            if self.solve_pure_negative(idx) {
+                ctx.record(Technique::HiddenSingle);
+                ctx.record_step(
+                    Technique::HiddenSingle,
+                    idx,
+                    before,
+                    self.grid[idx],
+                );
+            }
+
+            self.propagate(idx);
+        }
+
+        let mut collapsed = false;
+
+        for &idx in order {
+            if matches!(self.grid[idx], Cell::Superposition(_))
+                && let Some(value) = self.grid[idx].collapse()
+            {
+                ctx.record(Technique::NakedSingle);
+                ctx.record_step(
+                    Technique::NakedSingle,
+                    idx,
+                    self.grid[idx],
+                    Cell::Collapsed(value),
+                );
+
+                self.grid[idx] = Cell::Collapsed(value);
+                self.propagate(idx);
+                collapsed = true;
+            }
+
+            if self.grid[idx].count_superstates().unwrap_or(1) == 0 {
+                return None;
+            }
+        }
+
+        let progress = collapsed
+            || self.apply_eliminations(ctx)
+            || self.apply_strategies(ctx);
+
+        ctx.passes += 1;
+        Some(progress)
+    }
+
+    /// The cell to guess at once deduction stalls, with its candidates in the
+    /// order the search should try them. Only the first `len` values are
+    /// used
+    fn guesses(&self, ctx: &mut Search<'_>) -> (usize, [u8; BOARD_LEN], usize) {
+        let idx = self
+            .grid
+            .iter()
+            .position(|cell| matches!(cell, Cell::Superposition(_)))
+            .expect("No superstates found");
+
+        let Cell::Superposition(s) = self.grid[idx] else { unreachable!() };
+
+        let mut candidates = [0; BOARD_LEN];
+        let mut num_candidates = 0;
+
+        for (val_idx, _) in s.iter().enumerate().filter(|(_, val)| **val) {
+            candidates[num_candidates] =
+                u8::try_from(val_idx + 1).expect("Value out of range");
+            num_candidates += 1;
+        }
+
+        if let Some(rng) = ctx.rng.as_deref_mut() {
+            candidates[..num_candidates].shuffle(rng);
+        } else if ctx.descending {
+            candidates[..num_candidates].reverse();
+        }
+
+        (idx, candidates, num_candidates)
+    }
+
+    /// Depth-first search over the remaining superpositions. `visit` is called
+    /// on every solution found and decides whether the search continues
+    fn search(
+        &mut self,
+        ctx: &mut Search<'_>,
+        visit: &mut impl FnMut(&Self) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        if !self.deduce(ctx) {
+            return ControlFlow::Continue(());
+        }
+
+        if self.is_complete_and_correct() {
+            return visit(self);
+        }
+
+        if self.is_solved() {
+            return ControlFlow::Continue(());
+        }
+
+        if let Some(error) = ctx.interruption() {
+            ctx.error = Some(error);
+            return ControlFlow::Break(());
+        }
+
+        if !ctx.config.backtracking {
+            ctx.error = Some(SolveError::RequiresGuessing);
+            return ControlFlow::Break(());
+        }
+
+        // Backtrack
+        let (idx, candidates, num_candidates) = self.guesses(ctx);
+
+        for &possible_val in &candidates[..num_candidates] {
+            let mut clone = *self;
+            clone.grid[idx] = Cell::Collapsed(possible_val);
+
+            if ctx.config.forward_checking && !clone.survives_placement(idx) {
+                continue;
+            }
+
+            ctx.record(Technique::Guess);
+
+            if ctx
+                .config
+                .max_guesses
+                .is_some_and(|max| ctx.stats[Technique::Guess as usize] > max)
+            {
+                ctx.error = Some(SolveError::GuessLimitExceeded);
+                return ControlFlow::Break(());
+            }
+
+            let steps_before = ctx.steps.as_ref().map(Vec::len);
+            ctx.record_step(
+                Technique::Guess,
+                idx,
+                self.grid[idx],
+                clone.grid[idx],
+            );
+
+            clone.search(ctx, visit)?;
+
+            // Only the steps on the path to the solution are kept
+            if let (Some(steps), Some(len)) = (&mut ctx.steps, steps_before) {
+                steps.truncate(len);
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Looks ahead from the value just placed at `idx` by placing every naked
+    /// single it leads to, on a copy of the board. Returns `false` if that
+    /// leaves some cell with no candidates, proving the placement wrong
+    fn survives_placement(&self, idx: usize) -> bool {
+        let mut board = *self;
+        board.propagate(idx);
+
+        loop {
+            let mut placed = false;
+
+            for idx in 0..BOARD_SIZE {
+                match board.grid[idx].count_superstates() {
+                    Some(0) => return false,
+                    Some(1) => {
+                        if let Some(value) = board.grid[idx].collapse() {
+                            board.grid[idx] = Cell::Collapsed(value);
+                            board.propagate(idx);
+                            placed = true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if !placed {
+                return true;
+            }
+        }
+    }
+
+    /// Searches for the first solution reachable from the current state and
+    /// writes it back to `self`
+    fn search_first(&mut self, ctx: &mut Search<'_>) -> Result<(), SolveError> {
+        ctx.deadline =
+            ctx.config.time_limit.map(|limit| Instant::now() + limit);
+
+        if self.validate().is_err() {
+            return Err(SolveError::AlreadyInvalid);
+        }
+
+        if self.is_solved() {
+            return Ok(());
+        }
+
+        // A value with nowhere left to go in some unit is a cheap proof that
+        // there's no solution, without any guessing
+        let mut propagated = *self;
+        propagated.propagate_all();
+
+        if !propagated.every_value_has_a_cell() {
+            return Err(SolveError::NoSolution);
+        }
+
+        if ctx.config.uniqueness == UniquenessMode::RequireUnique {
+            match self.count_solutions(2) {
+                0 => return Err(SolveError::NoSolution),
+                1 => {}
+                _ => return Err(SolveError::NotUnique),
+            }
+        }
+
+        let mut solution = None;
+
+        let _ = self.search(ctx, &mut |board| {
+            solution = Some(*board);
+            ControlFlow::Break(())
+        });
+
+        if let Some(error) = ctx.error {
+            return Err(error);
+        }
+
+        *self = solution.ok_or(SolveError::NoSolution)?;
+        Ok(())
+    }
+
+    /// Solves the board using the default [`SolverConfig`]. Guesses are made
+    /// in the first open cell, smallest value first, so for puzzles with
+    /// several solutions this finds the lexicographically smallest one.
+    /// Boards that are already solved are left untouched
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::AlreadyInvalid`] if a value is already repeated
+    /// in some unit or breaks its cell's parity, or [`SolveError::NoSolution`]
+    /// if the board can't be completed
+    pub fn solve(&mut self) -> Result<(), SolveError> {
+        self.search_first(&mut Search::default())
+    }
+
+    /// Initializes the board's candidates and solves it like
+    /// [`Sudoku::solve`], returning the solved board so parsing and solving
+    /// can be chained without a mutable binding
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Sudoku::solve`] would
+    pub fn solve_into(mut self) -> Result<Self, SolveError> {
+        self.initialize_superpositions();
+        self.solve()?;
+        Ok(self)
+    }
+
+    /// Solves the board like [`Sudoku::solve`], but guesses the largest value
+    /// first to find the lexicographically largest solution. The two agree
+    /// exactly when the solution is unique, making this a cheap uniqueness
+    /// check that doesn't enumerate every solution
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NoSolution`] if the board can't be completed
+    pub fn solve_max(&mut self) -> Result<(), SolveError> {
+        self.search_first(&mut Search { descending: true, ..Search::default() })
+    }
+
+    /// Solves the board like [`Sudoku::solve`], but checks `cancel` between
+    /// deduction passes and before every guess and gives up once it is set,
+    /// so a solve running on another thread can be stopped. A flag that is
+    /// already set cancels the solve before any deduction is tried
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::Cancelled`] if `cancel` was set before a solution
+    /// was found, and otherwise fails as [`Sudoku::solve`] would
+    pub fn solve_cancellable(
+        &mut self,
+        cancel: &AtomicBool,
+    ) -> Result<(), SolveError> {
+        self.search_first(&mut Search {
+            cancel: Some(cancel),
+            ..Search::default()
+        })
+    }
+
+    /// Solves the board like [`Sudoku::solve`] and reports whether any
+    /// guesses were needed, i.e. `false` if the solution followed from logic
+    /// alone
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Sudoku::solve`] would
+    pub fn solve_classified(&mut self) -> Result<bool, SolveError> {
+        let mut ctx = Search::default();
+        self.search_first(&mut ctx)?;
+
+        Ok(ctx.stats[Technique::Guess as usize] > 0)
+    }
+
+    /// Solves the board, giving up as soon as any limit set in `config` is
+    /// reached
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NoSolution`] if the board can't be completed,
+    /// [`SolveError::GuessLimitExceeded`] if more than
+    /// [`SolverConfig::max_guesses`] guesses were needed,
+    /// [`SolveError::RequiresGuessing`] if a guess was needed but
+    /// [`SolverConfig::backtracking`] is disabled, and
+    /// [`SolveError::NotUnique`] if the puzzle is ambiguous under
+    /// [`UniquenessMode::RequireUnique`], and [`SolveError::Timeout`] if the
+    /// search ran past [`SolverConfig::time_limit`]
+    pub fn solve_with_config(
+        &mut self,
+        config: &SolverConfig,
+    ) -> Result<(), SolveError> {
+        self.search_first(&mut Search { config, ..Search::default() })
+    }
+
+    /// Solves the board like [`Sudoku::solve`], running `strategies` in order
+    /// whenever the built-in techniques stall, until none of them makes
+    /// progress either. Only then is a guess made. Progress made by a custom
+    /// strategy isn't counted towards any [`Technique`]
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Sudoku::solve`] would
+    pub fn solve_with_strategies(
+        &mut self,
+        strategies: &[Box<dyn Strategy>],
+    ) -> Result<(), SolveError> {
+        self.search_first(&mut Search { strategies, ..Search::default() })
+    }
+
+    /// Applies every deterministic technique until no more progress can be
+    /// made, without guessing. Returns whether the board ended up solved. If
+    /// it didn't, the partially solved board is left in place
+    pub fn try_solve_logic_only(&mut self) -> bool {
+        let config =
+            SolverConfig { backtracking: false, ..SolverConfig::new() };
+        let mut ctx = Search { config: &config, ..Search::default() };
+
+        // The top level of the search deduces in place rather than on a copy
+        let _ = self.search(&mut ctx, &mut |_| ControlFlow::Break(()));
+
+        self.is_complete_and_correct()
+    }
+
+    /// Solves the board like [`Sudoku::solve`], returning every placement and
+    /// elimination on the path to the solution in the order they were made.
+    /// Deductions made in branches that were later abandoned aren't included
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NoSolution`] if the board can't be completed
+    pub fn solve_steps(&mut self) -> Result<Vec<Step>, SolveError> {
+        let mut ctx = Search { steps: Some(Vec::new()), ..Search::default() };
+        self.search_first(&mut ctx)?;
+
+        Ok(ctx.steps.unwrap_or_default())
+    }
+
+    /// Solves the board like [`Sudoku::solve`], returning the guesses that
+    /// led to the solution as `(row, col, value)` in the order they were
+    /// made. Guesses abandoned during backtracking aren't included, so this
+    /// is empty exactly when the puzzle was solved by logic alone
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Sudoku::solve`] would
+    pub fn solve_guesses(
+        &mut self,
+    ) -> Result<Vec<(usize, usize, u8)>, SolveError> {
+        Ok(self
+            .solve_steps()?
+            .into_iter()
+            .filter(|step| step.technique == Technique::Guess)
+            .filter_map(|step| step.value.map(|n| (step.row, step.col, n)))
+            .collect())
+    }
+
+    /// Solves the board, returning the techniques used on the path to the
+    /// solution in the order each was first needed, e.g.
+    /// `[HiddenSingle, NakedSingle, Guess]`. If the board has no solution,
+    /// only the deductions made before the first guess are summarised
+    pub fn solve_path(&mut self) -> Vec<Technique> {
+        let mut ctx = Search { steps: Some(Vec::new()), ..Search::default() };
+        let _ = self.search_first(&mut ctx);
+
+        let mut path = Vec::new();
+
+        for step in ctx.steps.unwrap_or_default() {
+            if !path.contains(&step.technique) {
+                path.push(step.technique);
+            }
+        }
+
+        path
+    }
+
+    /// Solves the board like [`Sudoku::solve`], reporting how much progress
+    /// each technique contributed. Singles count the cells they filled,
+    /// elimination techniques count the candidates they removed, and guesses
+    /// include those made in branches that were later abandoned.
+    ///
+    /// Each placement is credited to whichever technique found it first, not
+    /// to the simplest one that could have. Every pass looks for hidden
+    /// singles before naked singles, but a naked single placed late in one
+    /// pass can still take a cell a hidden single would have found in the
+    /// next, so a puzzle solvable by hidden singles alone may report a few
+    /// naked singles too
+    pub fn solve_with_stats(&mut self) -> HashMap<Technique, usize> {
+        let mut ctx = Search::default();
+        let _ = self.search_first(&mut ctx);
+
+        Technique::ALL.into_iter().zip(ctx.stats).collect()
+    }
+
+    /// Calls `f` on each solution of the puzzle until it returns
+    /// [`ControlFlow::Break`] or the search space is exhausted
+    fn for_each_solution(&self, mut f: impl FnMut(&Self) -> ControlFlow<()>) {
+        let mut board = *self;
+        board.initialize_superpositions();
+
+        let _ = board.search(&mut Search::default(), &mut f);
+    }
+
+    /// Counts the solutions of the puzzle, stopping once `limit` have been
+    /// found
+    #[must_use]
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut count = 0;
+
+        if limit == 0 {
+            return count;
+        }
+
+        self.for_each_solution(|_| {
+            count += 1;
+
+            if count < limit {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+
+        count
+    }
+
+    /// The number of boards the backtracking search visits when it explores
+    /// every branch instead of stopping at the first solution, counting the
+    /// starting board. Branches pruned by forward checking aren't visited.
+    /// A puzzle solved by deduction alone has a tree of 1, and the size grows
+    /// with the amount of guessing needed, however many solutions there are.
+    ///
+    /// The tree grows very quickly as clues are removed, and a sparse board
+    /// can take minutes to explore. Use [`Sudoku::guess_tree_size_within`]
+    /// to give up past a size
+    #[must_use]
+    pub fn guess_tree_size(&self) -> usize {
+        self.explore_guess_tree(&DEFAULT_CONFIG).0
+    }
+
+    /// The size of the tree described by [`Sudoku::guess_tree_size`], or
+    /// `None` as soon as more than `max_nodes` boards have been visited
+    #[must_use]
+    pub fn guess_tree_size_within(&self, max_nodes: usize) -> Option<usize> {
+        let config = SolverConfig {
+            max_guesses: Some(max_nodes.checked_sub(1)?),
+            ..SolverConfig::new()
+        };
+
+        let (size, abandoned) = self.explore_guess_tree(&config);
+        (!abandoned).then_some(size)
+    }
+
+    /// The number of boards visited exploring every branch, and whether the
+    /// search was abandoned before it finished
+    fn explore_guess_tree(&self, config: &SolverConfig) -> (usize, bool) {
+        let mut board = *self;
+        board.initialize_superpositions();
+
+        let mut ctx = Search { config, ..Search::default() };
+        let _ = board.search(&mut ctx, &mut |_| ControlFlow::Continue(()));
+
+        // Every board after the first is entered through a guess
+        (1 + ctx.stats[Technique::Guess as usize], ctx.error.is_some())
+    }
+
+    /// Whether the puzzle has no solution, exactly one, or several. The
+    /// search stops at the second solution, so this costs no more than
+    /// `count_solutions(2)`
+    #[must_use]
+    pub fn solution_class(&self) -> SolutionClass {
+        match self.count_solutions(2) {
+            0 => SolutionClass::None,
+            1 => SolutionClass::Unique,
+            _ => SolutionClass::Multiple,
+        }
+    }
+
+    /// Returns up to `limit` distinct solutions of the puzzle
+    #[must_use]
+    pub fn solutions(&self, limit: usize) -> Vec<Self> {
+        let mut solutions = Vec::new();
+
+        if limit == 0 {
+            return solutions;
+        }
+
+        self.for_each_solution(|board| {
+            solutions.push(*board);
+
+            if solutions.len() < limit {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+
+        solutions
+    }
+
+    /// The solution at position `n`, counting from 0, in the order the
+    /// search finds them, or `None` if the puzzle has `n` solutions or fewer.
+    /// The order is deterministic, and the search stops as soon as the
+    /// solution is reached
+    #[must_use]
+    pub fn solve_nth(&self, n: usize) -> Option<Self> {
+        let mut remaining = n;
+        let mut found = None;
+
+        self.for_each_solution(|board| {
+            if remaining == 0 {
+                found = Some(*board);
+                ControlFlow::Break(())
+            } else {
+                remaining -= 1;
+                ControlFlow::Continue(())
+            }
+        });
+
+        found
+    }
+
+    /// Checks a player's progress on `puzzle`: returns the `(row, col)` of
+    /// every filled cell on this board whose value differs from the puzzle's
+    /// solution, i.e. the mistakes a "check my work" button should highlight.
+    /// Empty cells are never flagged. If `puzzle` doesn't have exactly one
+    /// solution there is nothing to check against, so nothing is flagged
+    #[must_use]
+    pub fn check_progress(&self, puzzle: &Self) -> Vec<(usize, usize)> {
+        let [solution] = puzzle.solutions(2)[..] else {
+            return Vec::new();
+        };
+
+        (0..BOARD_SIZE)
+            .filter(|&idx| {
+                self.grid[idx]
+                    .value()
+                    .is_some_and(|n| solution.grid[idx].value() != Some(n))
+            })
+            .map(Self::idx_to_coord)
+            .collect()
+    }
+
+    /// Returns the `(row, col)` of every cell that differs between the first
+    /// two solutions of the puzzle. These are the cells where another clue
+    /// would help make the solution unique. Empty if the puzzle has fewer
+    /// than two solutions
+    #[must_use]
+    pub fn ambiguous_cells(&self) -> Vec<(usize, usize)> {
+        let [first, second] = self.solutions(2)[..] else {
+            return Vec::new();
+        };
+
+        (0..BOARD_SIZE)
+            .filter(|&idx| first.grid[idx].value() != second.grid[idx].value())
+            .map(Self::idx_to_coord)
+            .collect()
+    }
+}
+
+static DEFAULT_CONFIG: SolverConfig = SolverConfig::new();
+
+/// State shared across the branches of a backtracking search
+struct Search<'a> {
+    config: &'a SolverConfig,
+
+    /// Shuffles the candidate order at each guess when present
+    rng: Option<&'a mut dyn Rng>,
+
+    /// Guess the largest candidate first rather than the smallest
+    descending: bool,
+
+    /// Number of times each technique made progress, indexed by `Technique`
+    stats: [usize; Technique::ALL.len()],
+
+    /// Number of deterministic passes made over the board
+    passes: usize,
+
+    /// Why the search was abandoned before finding a solution, if it was
+    error: Option<SolveError>,
+
+    /// The deductions leading to the current state, when recording is enabled
+    steps: Option<Vec<Step>>,
+
+    /// Abandons the search after the current deduction pass once set
+    cancel: Option<&'a AtomicBool>,
+
+    /// Abandons the search after the current deduction pass once passed
+    deadline: Option<Instant>,
+
+    /// Custom techniques tried once the built-in ones stall
+    strategies: &'a [Box<dyn Strategy>],
+}
+
+impl Default for Search<'_> {
+    fn default() -> Self {
+        Self {
+            config: &DEFAULT_CONFIG,
+            rng: None,
+            descending: false,
+            stats: [0; Technique::ALL.len()],
+            passes: 0,
+            error: None,
+            steps: None,
+            cancel: None,
+            deadline: None,
+            strategies: &[],
+        }
+    }
+}
+
+impl Search<'_> {
+    /// Logs the cell at `idx` changing from `before` to `after`
+    fn record_step(
+        &mut self,
+        technique: Technique,
+        idx: usize,
+        before: Cell,
+        after: Cell,
+    ) {
+        if let Some(steps) = &mut self.steps {
+            let (row, col) = Sudoku::idx_to_coord(idx);
+
+            steps.push(Step {
+                technique,
+                row,
+                col,
+                value: after.value(),
+                eliminated: before.candidate_mask() & !after.candidate_mask(),
+            });
+        }
+    }
+
+    /// Why the search should stop where it is, if the caller cancelled it or
+    /// its time ran out
+    fn interruption(&self) -> Option<SolveError> {
+        if self.cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return Some(SolveError::Cancelled);
+        }
+
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Some(SolveError::Timeout);
+        }
+
+        None
+    }
+
+    const fn record(&mut self, technique: Technique) {
+        self.record_many(technique, 1);
+    }
+
+    const fn record_many(&mut self, technique: Technique, count: usize) {
+        self.stats[technique as usize] += count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        thread,
+        time::Duration,
+    };
+
+    use crate::{
+        BOARD_SIZE, Cell, Error, SolutionClass, SolveError, Sudoku, Technique,
+    };
+
+    const EASY: &str = "000260701680070090190004500820100040004602900050003028009300074040050036703018000";
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    const HARD_SOLUTION: &str = "126437958895621473374985126457193862983246517612578394269314785548769231731852649";
+
+    fn initialized(puzzle: &str) -> Sudoku {
+        let mut board: Sudoku = puzzle.parse().unwrap();
+        board.initialize_superpositions();
+        board
+    }
+
+    /// [`EASY`] without its first clue, which leaves it with several solutions
+    fn ambiguous() -> Sudoku {
+        let mut board: Sudoku = EASY.parse().unwrap();
+        assert!(board.remove_clue(0, 3));
+        board.initialize_superpositions();
+        board
+    }
+
+    #[test]
+    fn hidden_single_skips_stale_candidates() {
+        let mut board: Sudoku =
+            format!("12345678{}", ".".repeat(73)).parse().unwrap();
+        board.initialize_superpositions();
+
+        // Every peer of (0, 8) in its row is filled, but nothing has been
+        // propagated, so 1 looks like a hidden single there
+        assert!(!board.solve_pure_negative(8));
+        assert_eq!(board.grid[8].candidate_mask(), 0b1_1111_1110);
+
+        board.solve().unwrap();
+        assert!(board.is_complete_and_correct());
+        assert_eq!(board.grid[8], Cell::Collapsed(9));
+    }
+
+    #[test]
+    fn contradictory_grid_has_no_solution() {
+        // Row 1 leaves only 9 for R1C9, but column 9 already holds a 9. No
+        // value is repeated, so this only shows up while solving
+        let mut board: Sudoku =
+            format!("12345678.{}9{}", ".".repeat(44), ".".repeat(27))
+                .parse()
+                .unwrap();
+
+        assert!(board.validate().is_ok());
+
+        board.initialize_superpositions();
+        assert_eq!(board.solve(), Err(SolveError::NoSolution));
+        assert!(!board.is_complete_and_correct());
+    }
+
+    #[test]
+    fn stats_credit_the_technique_that_found_each_placement() {
+        let mut board: Sudoku = EASY.parse().unwrap();
+        board.initialize_superpositions();
+
+        let stats = board.solve_with_stats();
+        assert!(board.is_complete_and_correct());
+
+        // Both kinds of single find cells on a puzzle hidden singles alone
+        // could solve, and between them they fill every blank
+        assert_eq!(stats[&Technique::HiddenSingle], 42);
+        assert_eq!(stats[&Technique::NakedSingle], 3);
+
+        for (technique, count) in stats {
+            if !matches!(
+                technique,
+                Technique::HiddenSingle | Technique::NakedSingle
+            ) {
+                assert_eq!(count, 0, "{technique:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn cancel_flag_set_from_another_thread_stops_the_solve() {
+        let cancel = AtomicBool::new(false);
+
+        let error = thread::scope(|scope| {
+            // Solves until one is cancelled, which can only happen once the
+            // flag is set below
+            let solver = scope.spawn(|| {
+                loop {
+                    if let Err(error) =
+                        initialized(HARD).solve_cancellable(&cancel)
+                    {
+                        return error;
+                    }
+                }
+            });
+
+            thread::sleep(Duration::from_millis(10));
+            cancel.store(true, Ordering::Relaxed);
+
+            solver.join().unwrap()
+        });
+
+        assert_eq!(error, SolveError::Cancelled);
+    }
+
+    #[test]
+    fn cancel_flag_set_beforehand_stops_a_logic_solve() {
+        let mut board = initialized(EASY);
+
+        assert_eq!(
+            board.solve_cancellable(&AtomicBool::new(true)),
+            Err(SolveError::Cancelled)
+        );
+        assert!(!board.is_solved());
+
+        assert_eq!(board.solve_cancellable(&AtomicBool::new(false)), Ok(()));
+        assert!(board.is_complete_and_correct());
+    }
+
+    #[test]
+    fn guess_tree_is_minimal_without_guessing() {
+        let puzzle: Sudoku = EASY.parse().unwrap();
+        assert_eq!(puzzle.guess_tree_size(), 1);
+
+        // Without its first clue the puzzle is ambiguous, and every solution
+        // is reached through a guess
+        let mut ambiguous = puzzle;
+        assert!(ambiguous.remove_clue(0, 3));
+        assert_eq!(ambiguous.solution_class(), SolutionClass::Multiple);
+        assert!(ambiguous.guess_tree_size() > 1);
+    }
+
+    #[test]
+    fn guess_tree_size_gives_up_past_the_limit() {
+        let mut sparse: Sudoku = EASY.parse().unwrap();
+
+        for col in [3, 4, 6, 8] {
+            assert!(sparse.remove_clue(0, col));
+        }
+
+        let size = sparse.guess_tree_size();
+        assert!(size > 2);
+
+        assert_eq!(sparse.guess_tree_size_within(size), Some(size));
+        assert_eq!(sparse.guess_tree_size_within(size - 1), None);
+        assert_eq!(sparse.guess_tree_size_within(0), None);
+    }
+
+    #[test]
+    fn smallest_and_largest_solutions_agree_only_when_unique() {
+        let extremes = |board: Sudoku| {
+            let (mut min, mut max) = (board, board);
+            min.solve().unwrap();
+            max.solve_max().unwrap();
+            (min, max)
+        };
+
+        let (min, max) = extremes(initialized(HARD));
+        assert!(min.is_complete_and_correct());
+        assert_eq!(min.grid, max.grid);
+
+        let (min, max) = extremes(ambiguous());
+        assert!(min.is_complete_and_correct());
+        assert!(max.is_complete_and_correct());
+        assert!(min.to_line() < max.to_line());
+    }
+
+    #[test]
+    fn solve_path_includes_guess_only_when_one_was_needed() {
+        let path = initialized(EASY).solve_path();
+        assert!(path.contains(&Technique::HiddenSingle));
+        assert!(!path.contains(&Technique::Guess));
+
+        assert!(initialized(HARD).solve_path().contains(&Technique::Guess));
+    }
+
+    #[test]
+    fn logic_only_solve_stops_where_guessing_would_start() {
+        let mut easy = initialized(EASY);
+        assert!(easy.try_solve_logic_only());
+        assert!(easy.is_complete_and_correct());
+
+        let puzzle = initialized(HARD);
+        let mut hard = puzzle;
+        assert!(!hard.try_solve_logic_only());
+
+        // The progress made is kept, leaving a board that is partly filled
+        // in but still open
+        let filled =
+            |board: &Sudoku| board.grid.iter().filter_map(Cell::value).count();
+        assert!(filled(&hard) > filled(&puzzle));
+        assert!(!hard.is_solved());
+        assert!(puzzle.is_subset_of(&hard));
+    }
+
+    #[test]
+    fn solve_classified_reports_whether_a_guess_was_needed() {
+        let mut easy = initialized(EASY);
+        assert_eq!(easy.solve_classified(), Ok(false));
+        assert!(easy.is_complete_and_correct());
+
+        let mut hard = initialized(HARD);
+        assert_eq!(hard.solve_classified(), Ok(true));
+        assert!(hard.is_complete_and_correct());
+    }
+
+    #[test]
+    fn solve_returns_early_on_finished_and_invalid_boards() {
+        let solved = initialized(HARD_SOLUTION);
+
+        let mut board = solved;
+        assert_eq!(board.solve(), Ok(()));
+        assert_eq!(board.grid, solved.grid);
+
+        let invalid = initialized(&format!("11{}", ".".repeat(79)));
+
+        let mut board = invalid;
+        assert_eq!(board.solve(), Err(SolveError::AlreadyInvalid));
+        assert_eq!(board.grid, invalid.grid);
+    }
+
+    #[test]
+    fn solution_class_tells_none_unique_and_multiple_apart() {
+        let contradictory =
+            format!("12345678.{}9{}", ".".repeat(44), ".".repeat(27));
+
+        assert_eq!(
+            initialized(&contradictory).solution_class(),
+            SolutionClass::None
+        );
+        assert_eq!(initialized(EASY).solution_class(), SolutionClass::Unique);
+        assert_eq!(initialized(HARD).solution_class(), SolutionClass::Unique);
+        assert_eq!(ambiguous().solution_class(), SolutionClass::Multiple);
+    }
+
+    #[test]
+    fn nth_solution_indexes_the_solutions_in_order() {
+        let unique = initialized(HARD);
+        let solution = unique.solve_into().unwrap();
+
+        assert_eq!(unique.solve_nth(0).unwrap().grid, solution.grid);
+        assert!(unique.solve_nth(1).is_none());
+
+        let ambiguous = ambiguous();
+        let first = ambiguous.solve_nth(0).unwrap();
+        let second = ambiguous.solve_nth(1).unwrap();
+
+        assert!(first.is_complete_and_correct());
+        assert!(second.is_complete_and_correct());
+        assert_ne!(first.grid, second.grid);
+
+        let solutions = ambiguous.solutions(2);
+        assert_eq!(solutions[0].grid, first.grid);
+        assert_eq!(solutions[1].grid, second.grid);
+    }
+
+    #[test]
+    fn ambiguous_cells_are_where_two_solutions_differ() {
+        assert!(initialized(EASY).ambiguous_cells().is_empty());
+
+        let ambiguous = ambiguous();
+        let cells = ambiguous.ambiguous_cells();
+        assert!(!cells.is_empty());
+
+        let [first, second] = ambiguous.solutions(2)[..] else {
+            panic!("expected two solutions");
+        };
+
+        for (idx, (a, b)) in first.grid.iter().zip(&second.grid).enumerate() {
+            let coord = Sudoku::idx_to_coord(idx);
+            assert_eq!(a != b, cells.contains(&coord), "{coord:?}");
+        }
+    }
+
+    #[test]
+    fn initialized_once_no_empty_cell_remains() {
+        let mut board: Sudoku = HARD.parse().unwrap();
+        assert!(!board.is_initialized());
+
+        board.initialize_superpositions();
+        assert!(board.is_initialized());
+
+        // A full board has nothing to initialize
+        let solution: Sudoku = HARD_SOLUTION.parse().unwrap();
+        assert!(solution.is_initialized());
+    }
+
+    #[test]
+    fn full_board_with_a_repeat_is_not_correct() {
+        let solution: Sudoku = HARD_SOLUTION.parse().unwrap();
+        assert!(solution.is_complete_and_correct());
+
+        // Swapping the first two values keeps every cell filled but repeats
+        // both in their columns
+        let swapped = format!("21{}", &HARD_SOLUTION[2..]);
+        let board: Sudoku = swapped.parse().unwrap();
+
+        assert!(board.is_solved());
+        assert!(!board.is_complete_and_correct());
+    }
+
+    #[test]
+    fn box_of_numbers_subsections_in_row_major_order() {
+        assert_eq!(Sudoku::box_of(0, 0), 0);
+        assert_eq!(Sudoku::box_of(2, 8), 2);
+        assert_eq!(Sudoku::box_of(4, 7), 5);
+        assert_eq!(Sudoku::box_of(8, 0), 6);
+        assert_eq!(Sudoku::box_of(8, 8), 8);
+    }
+
+    #[test]
+    fn only_clues_are_given_after_a_solve() {
+        let puzzle: Sudoku = HARD.parse().unwrap();
+        let solution = puzzle.solve_into().unwrap();
+
+        for (idx, c) in HARD.chars().enumerate() {
+            let (row, col) = Sudoku::idx_to_coord(idx);
+            assert_eq!(solution.is_given(row, col), c != '0', "{row}, {col}");
+        }
+    }
+
+    #[test]
+    fn values_eq_treats_clues_and_placements_alike() {
+        let solved = HARD.parse::<Sudoku>().unwrap().solve_into().unwrap();
+        let expected: Sudoku = HARD_SOLUTION.parse().unwrap();
+
+        assert_ne!(solved.grid, expected.grid);
+        assert!(solved.values_eq(&expected));
+        assert!(expected.values_eq(&solved));
+
+        let puzzle = initialized(HARD);
+        assert!(!puzzle.values_eq(&expected));
+    }
+
+    #[test]
+    fn diff_against_the_solution_lists_every_blank() {
+        let puzzle: Sudoku = HARD.parse().unwrap();
+        let solution: Sudoku = HARD_SOLUTION.parse().unwrap();
+
+        let diff = puzzle.diff(&solution);
+        assert_eq!(diff.len(), puzzle.empty_cells().len());
+
+        for (row, col, old, new) in diff {
+            let idx = Sudoku::coord_to_idx((row, col));
+            assert_eq!(old, 0);
+            assert_eq!(Some(new), solution.grid[idx].value());
+        }
+
+        let solved = puzzle.solve_into().unwrap();
+        assert!(solved.diff(&solution).is_empty());
+    }
+
+    #[test]
+    fn sees_agrees_with_peers_for_every_pair() {
+        let board = Sudoku::empty();
+
+        for a in 0..BOARD_SIZE {
+            let peers: Vec<usize> = board.peers(a).collect();
+
+            for b in 0..BOARD_SIZE {
+                assert_eq!(board.sees(a, b), peers.contains(&b), "{a}, {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn solve_into_chains_after_parsing() {
+        fn solve(puzzle: &str) -> Result<Sudoku, Error> {
+            Ok(puzzle.parse::<Sudoku>()?.solve_into()?)
+        }
+
+        let solution: Sudoku = HARD_SOLUTION.parse().unwrap();
+        assert!(solve(HARD).unwrap().values_eq(&solution));
+
+        let contradictory =
+            format!("12345678.{}9{}", ".".repeat(44), ".".repeat(27));
+        assert_eq!(
+            solve(&contradictory).unwrap_err(),
+            Error::Solve(SolveError::NoSolution)
+        );
+        assert!(matches!(solve("12"), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn removing_a_clue_gives_a_subset() {
+        let puzzle: Sudoku = HARD.parse().unwrap();
+        let mut fewer = puzzle;
+        assert!(fewer.remove_clue(0, 1));
+
+        assert!(fewer.is_subset_of(&puzzle));
+        assert!(!puzzle.is_subset_of(&fewer));
+        assert!(puzzle.is_subset_of(&puzzle));
+
+        // A clue has to keep its value, not just its cell
+        let changed = puzzle.with_clue(0, 1, 3);
+        assert!(!puzzle.is_subset_of(&changed));
+        assert!(!changed.is_subset_of(&puzzle));
+    }
+
+    #[test]
+    fn empty_cells_are_the_blanks_in_order() {
+        let puzzle = initialized(HARD);
+        let blanks = puzzle.empty_cells();
+
+        assert_eq!(blanks.len(), 62);
+        assert_eq!(blanks.len() + puzzle.clue_count(), BOARD_SIZE);
+        assert_eq!(blanks[..2], [(0, 0), (0, 2)]);
+        assert!(blanks.is_sorted());
+
+        let solved = puzzle.solve_into().unwrap();
+        assert!(solved.empty_cells().is_empty());
+    }
+
+    #[test]
+    fn iterating_a_board_gives_its_cells_in_row_major_order() {
+        let board = initialized(HARD);
+
+        let by_ref: Vec<Cell> = (&board).into_iter().copied().collect();
+        let by_value: Vec<Cell> = board.into_iter().collect();
+
+        assert_eq!(by_value.len(), BOARD_SIZE);
+        assert_eq!(by_ref, by_value);
+        assert_eq!(by_value, board.grid);
+
+        let digits: String = board
+            .into_iter()
+            .map(|cell| cell.value().map_or('0', |n| char::from(b'0' + n)))
+            .collect();
+        assert_eq!(digits, HARD);
+    }
+
+    #[test]
+    fn guess_trail_is_empty_only_when_logic_suffices() {
+        let mut easy = initialized(EASY);
+        assert!(easy.solve_guesses().unwrap().is_empty());
+        assert!(easy.is_complete_and_correct());
+
+        let mut hard = initialized(HARD);
+        let guesses = hard.solve_guesses().unwrap();
+        assert!(!guesses.is_empty());
+
+        // Abandoned guesses are left out, so every one agrees with the answer
+        let solution: Sudoku = HARD_SOLUTION.parse().unwrap();
+        for (row, col, n) in guesses {
+            let idx = Sudoku::coord_to_idx((row, col));
+            assert_eq!(solution.grid[idx].value(), Some(n), "{row}, {col}");
+        }
+    }
+
+    #[test]
+    fn check_progress_flags_only_wrong_entries() {
+        let puzzle: Sudoku = HARD.parse().unwrap();
+
+        // The solution has 1 at (0, 0) and 6 at (0, 2)
+        let player = puzzle.with_clue(0, 0, 1).with_clue(0, 2, 5);
+        assert_eq!(player.check_progress(&puzzle), [(0, 2)]);
+
+        assert!(puzzle.check_progress(&puzzle).is_empty());
+
+        let solution: Sudoku = HARD_SOLUTION.parse().unwrap();
+        assert!(solution.check_progress(&puzzle).is_empty());
+
+        // Without a unique solution there is nothing to check against
+        let ambiguous = ambiguous();
+        let wrong = ambiguous.with_clue(0, 0, 9);
+        assert!(wrong.check_progress(&ambiguous).is_empty());
+    }
+
+    #[test]
+    fn too_few_clues_are_warned_about() {
+        let solution: Sudoku = HARD_SOLUTION.parse().unwrap();
+        let first = |n| {
+            let keep: Vec<_> = (0..n).map(Sudoku::idx_to_coord).collect();
+            solution.mask_solution(&keep)
+        };
+
+        assert_eq!(first(16).warn_if_too_few_clues(), Some(16));
+        assert_eq!(first(17).warn_if_too_few_clues(), None);
+        assert_eq!(first(25).warn_if_too_few_clues(), None);
+        assert_eq!(Sudoku::empty().warn_if_too_few_clues(), Some(0));
+    }
+
+    #[test]
+    fn common_peers_of_a_row_pair_and_an_unrelated_pair() {
+        let board = Sudoku::empty();
+
+        // (0, 0) and (0, 4) share only row 0, so the rest of the row is
+        // common to both
+        let row: Vec<usize> = (1..9).filter(|&col| col != 4).collect();
+        assert_eq!(board.common_peers(0, 4), row);
+
+        // (0, 0) and (4, 4) share nothing; they meet at (0, 4) and (4, 0)
+        assert_eq!(board.common_peers(0, 40), [4, 36]);
+
+        // Cells in the same row and subsection share both
+        assert_eq!(board.common_peers(0, 1).len(), 7 + 6);
+    }
+
+    #[test]
+    fn checked_conversions_reject_cells_off_the_board() {
+        assert_eq!(Sudoku::try_coord_to_idx(4, 7), Some(43));
+        assert_eq!(Sudoku::try_coord_to_idx(8, 8), Some(BOARD_SIZE - 1));
+        assert_eq!(Sudoku::try_coord_to_idx(9, 0), None);
+        assert_eq!(Sudoku::try_coord_to_idx(0, 9), None);
+
+        assert_eq!(Sudoku::try_idx_to_coord(43), Some((4, 7)));
+        assert_eq!(Sudoku::try_idx_to_coord(BOARD_SIZE - 1), Some((8, 8)));
+        assert_eq!(Sudoku::try_idx_to_coord(BOARD_SIZE), None);
+
+        for idx in 0..BOARD_SIZE {
+            let (row, col) = Sudoku::try_idx_to_coord(idx).unwrap();
+            assert_eq!(Sudoku::try_coord_to_idx(row, col), Some(idx));
+        }
+    }
+}
@@ -1,404 +1,253 @@
 #![warn(clippy::pedantic, clippy::nursery)]
 
-use colored::Colorize;
+use std::{
+    env, fs,
+    process::ExitCode,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use rand::{SeedableRng, rngs::StdRng};
+use simple_sudoku_solver::{Difficulty, SolutionClass, SolveError, Sudoku};
+
+const USAGE: &str = "\
+Usage:
+    simple_sudoku_solver
+    simple_sudoku_solver generate [--difficulty easy|medium|hard] [--count N] [--seed N]
+    simple_sudoku_solver rate <file>";
 
-const ROW_SEP: &str = "+-------+-------+-------+";
+// Easy grid
+// const EXAMPLE_GRID: [[u8; 9]; 9] = [
+//     [0, 0, 0, 2, 6, 0, 7, 0, 1],
+//     [6, 8, 0, 0, 7, 0, 0, 9, 0],
+//     [1, 9, 0, 0, 0, 4, 5, 0, 0],
+//     [8, 2, 0, 1, 0, 0, 0, 4, 0],
+//     [0, 0, 4, 6, 0, 2, 9, 0, 0],
+//     [0, 5, 0, 0, 0, 3, 0, 2, 8],
+//     [0, 0, 9, 3, 0, 0, 0, 7, 4],
+//     [0, 4, 0, 0, 5, 0, 0, 3, 6],
+//     [7, 0, 3, 0, 1, 8, 0, 0, 0],
+// ];
 
-const BOARD_SEP: usize = 3;
-const BOARD_LEN: usize = BOARD_SEP * BOARD_SEP;
-const BOARD_SIZE: usize = BOARD_LEN * BOARD_LEN;
+// Izzy's grid
+// const SAMPLE_GRID: [[u8; 9]; 9] = [
+//     [4, 0, 7, 5, 0, 0, 0, 3, 0],
+//     [0, 1, 0, 0, 7, 0, 0, 0, 0],
+//     [0, 0, 0, 0, 0, 8, 0, 0, 0],
+//     [1, 0, 0, 2, 0, 6, 4, 0, 0],
+//     [0, 0, 0, 0, 0, 0, 0, 0, 7],
+//     [0, 8, 0, 3, 0, 0, 0, 1, 9],
+//     [0, 3, 0, 0, 5, 0, 0, 4, 8],
+//     [2, 0, 0, 0, 1, 0, 0, 9, 5],
+//     [0, 0, 0, 0, 0, 0, 0, 0, 6],
+// ];
+
+// Hard grid
+const SAMPLE_GRID: [[u8; 9]; 9] = [
+    [0, 2, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 6, 0, 0, 0, 0, 3],
+    [0, 7, 4, 0, 8, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 3, 0, 0, 2],
+    [0, 8, 0, 0, 4, 0, 0, 1, 0],
+    [6, 0, 0, 5, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 1, 0, 7, 8, 0],
+    [5, 0, 0, 0, 0, 9, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 4, 0],
+];
 
-#[derive(Debug, Default, Clone, Copy)]
-enum Cell {
-    #[default]
-    Empty,
-    Fixed(u8),
-    Collapsed(u8),
-    Superposition([bool; BOARD_LEN]),
+struct Benchmark {
+    elapsed: Duration,
+    iters: u32,
 }
 
-impl Cell {
-    fn count_superstates(&self) -> Option<usize> {
-        match self {
-            Self::Superposition(s) => Some(s.iter().filter(|&&x| x).count()),
-            _ => None,
-        }
+impl Benchmark {
+    fn average(&self) -> Duration {
+        self.elapsed / self.iters.max(1)
     }
+}
 
-    fn collapse(&self) -> Option<u8> {
-        match self {
-            Self::Superposition(s) => {
-                let mut count = 0;
-                let mut value = 0;
-
-                for (idx, val) in s.iter().enumerate() {
-                    if *val {
-                        count += 1;
-                        value = idx + 1;
-                    }
-                }
+/// Repeatedly solves `grid` until `target_time` has passed. At least one
+/// solve always completes, even if it alone takes longer than `target_time`
+fn benchmark(
+    grid: &[[u8; 9]; 9],
+    target_time: Duration,
+) -> Result<Benchmark, SolveError> {
+    let mut iters = 0;
 
-                if count == 1 {
-                    Some(u8::try_from(value).expect("Value out of range"))
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        }
-    }
-}
+    let start = Instant::now();
 
-impl std::fmt::Display for Cell {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Empty => write!(f, " "),
-            Self::Fixed(n) => write!(f, "{}", n.to_string().green()),
-            Self::Collapsed(n) => write!(f, "{}", n.to_string().yellow()),
-            Self::Superposition(_) => write!(f, "{}", "+".red()),
-        }
+    while iters == 0 || start.elapsed() < target_time {
+        let mut sudoku = Sudoku::from_zero_grid(grid);
+        sudoku.initialize_superpositions();
+        sudoku.solve()?;
+
+        iters += 1;
     }
-}
 
-#[derive(Debug, Copy, Clone)]
-struct Sudoku {
-    grid: [Cell; BOARD_SIZE],
+    Ok(Benchmark { elapsed: start.elapsed(), iters })
 }
 
-impl std::default::Default for Sudoku {
-    fn default() -> Self {
-        Self { grid: [const { Cell::Empty }; BOARD_SIZE] }
-    }
+/// Options for the `generate` command
+struct GenerateArgs {
+    difficulty: Option<Difficulty>,
+    count: usize,
+    seed: Option<u64>,
 }
 
-impl Sudoku {
-    fn from_zero_grid(grid: &[[u8; BOARD_LEN]; BOARD_LEN]) -> Self {
-        let mut sudoku = Self::default();
+impl GenerateArgs {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut parsed = Self { difficulty: None, count: 1, seed: None };
+        let mut args = args.iter();
 
-        let mut idx = 0;
+        while let Some(flag) = args.next() {
+            let mut value = || {
+                args.next().ok_or_else(|| format!("Missing value for `{flag}`"))
+            };
 
-        for row in grid {
-            for cell in row {
-                sudoku.grid[idx] =
-                    if *cell == 0 { Cell::Empty } else { Cell::Fixed(*cell) };
-
-                idx += 1;
+            match flag.as_str() {
+                "--difficulty" => {
+                    parsed.difficulty = Some(parse_difficulty(value()?)?);
+                }
+                "--count" => parsed.count = parse_number(flag, value()?)?,
+                "--seed" => parsed.seed = Some(parse_number(flag, value()?)?),
+                _ => return Err(format!("Unknown option `{flag}`")),
             }
         }
 
-        sudoku
-    }
-
-    const fn coord_to_idx((row, col): (usize, usize)) -> usize {
-        row * BOARD_LEN + col
-    }
-
-    const fn idx_to_coord(idx: usize) -> (usize, usize) {
-        let row = idx / BOARD_LEN;
-        let col = idx % BOARD_LEN;
-        (row, col)
-    }
-
-    const fn row_idx(idx: usize) -> usize {
-        let row = idx / BOARD_LEN;
-        row * BOARD_LEN
-    }
-
-    const fn col_idx(idx: usize) -> usize {
-        idx % BOARD_LEN
+        Ok(parsed)
     }
+}
 
-    const fn subsection_idx(idx: usize) -> usize {
-        let (mut row, mut col) = Self::idx_to_coord(idx);
-        row -= row % BOARD_SEP;
-        col -= col % BOARD_SEP;
-        row * BOARD_LEN + col
+fn parse_difficulty(value: &str) -> Result<Difficulty, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "easy" => Ok(Difficulty::Easy),
+        "medium" => Ok(Difficulty::Medium),
+        "hard" => Ok(Difficulty::Hard),
+        _ => Err(format!("Unknown difficulty `{value}`")),
     }
+}
 
-    fn is_solved(&self) -> bool {
-        self.grid
-            .iter()
-            .all(|cell| matches!(cell, Cell::Fixed(_) | Cell::Collapsed(_)))
-    }
+fn parse_number<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid value `{value}` for `{flag}`"))
+}
 
-    fn initialize_superpositions(&mut self) {
-        self.grid.iter_mut().for_each(|cell| match cell {
-            Cell::Fixed(_) => (),
-            Cell::Empty => *cell = Cell::Superposition([true; 9]),
-            _ => unreachable!(),
-        });
-    }
+/// Prints `count` puzzles, one per line. The same seed always produces the
+/// same puzzles; without one a random seed is used
+fn generate(args: &[String]) -> Result<(), String> {
+    let args = GenerateArgs::parse(args)?;
+    let mut rng = StdRng::seed_from_u64(args.seed.unwrap_or_else(rand::random));
 
-    fn propagate(&mut self, idx: usize) {
-        let (Cell::Fixed(n) | Cell::Collapsed(n)) = self.grid[idx] else {
-            return;
+    for _ in 0..args.count {
+        let puzzle = match args.difficulty {
+            Some(target) => Sudoku::generate_with_difficulty(target, &mut rng),
+            None => Sudoku::generate(&mut rng),
         };
 
-        // Nothing horizontally can be the same
-        let row_idx = Self::row_idx(idx);
-        for col in row_idx..row_idx + BOARD_LEN {
-            if let &mut Cell::Superposition(ref mut s) = &mut self.grid[col] {
-                s[n as usize - 1] = false;
-            }
-        }
+        println!("{}", puzzle.to_line());
+    }
 
-        // Nothing vertically can be the same
-        let col_idx = Self::col_idx(idx);
-        for row in (col_idx..BOARD_SIZE).step_by(BOARD_LEN) {
-            if let &mut Cell::Superposition(ref mut s) = &mut self.grid[row] {
-                s[n as usize - 1] = false;
-            }
-        }
+    Ok(())
+}
 
-        // Nothing in the same subsection can be the same
-        let subsection_idx = Self::subsection_idx(idx);
-        let (row, col) = Self::idx_to_coord(subsection_idx);
+fn run_benchmark() {
+    let result = benchmark(&SAMPLE_GRID, Duration::from_secs(5))
+        .expect("Sample grid is solvable");
 
-        for row in row..row + BOARD_SEP {
-            for col in col..col + BOARD_SEP {
-                if let &mut Cell::Superposition(ref mut s) =
-                    &mut self.grid[Self::coord_to_idx((row, col))]
-                {
-                    s[n as usize - 1] = false;
-                }
-            }
-        }
-    }
+    println!("Elapsed: {:?}", result.elapsed);
+    println!("Average: {:?}", result.average());
 
-    fn solve_pure_negative(&mut self, idx: usize) {
-        // If no other cell in the same row/col/subsection can have a certain
-        // value, this cell must have that value
+    let mut sudoku = Sudoku::from_zero_grid(&SAMPLE_GRID);
 
-        let Cell::Superposition(superposition) = self.grid[idx] else {
-            return;
-        };
+    println!("{sudoku}");
 
-        for (val_idx, _) in
-            superposition.iter().enumerate().filter(|(_, val)| **val)
-        {
-            let mut num_alternatives = 0;
-
-            // Nothing horizontally can be the same
-            let row_idx = Self::row_idx(idx);
-            for col in row_idx..row_idx + BOARD_LEN {
-                if let &mut Cell::Superposition(ref mut s) = &mut self.grid[col]
-                {
-                    if col != idx && s[val_idx] {
-                        num_alternatives += 1;
-                    }
-                }
-            }
+    sudoku.initialize_superpositions();
+    sudoku.solve().expect("Sample grid is solvable");
 
-            if num_alternatives == 0 {
-                // Include the current cell
-                self.grid[idx] = Cell::Collapsed(
-                    u8::try_from(val_idx + 1).expect("Value out of range"),
-                );
-                break;
-            }
+    println!("{sudoku}");
+}
 
-            // Nothing vertically can be the same
-            let col_idx = Self::col_idx(idx);
-            num_alternatives = 0;
-            for row in (col_idx..BOARD_SIZE).step_by(BOARD_LEN) {
-                if let &mut Cell::Superposition(ref mut s) = &mut self.grid[row]
-                {
-                    if row != idx && s[val_idx] {
-                        num_alternatives += 1;
-                    }
-                }
-            }
+/// Prints the clue count, validity, uniqueness and difficulty of the puzzle
+/// in `path`, in the line or nine-row format, without solving it visibly
+fn rate(args: &[String]) -> Result<(), String> {
+    let [path] = args else {
+        return Err("Expected exactly one puzzle file".to_string());
+    };
 
-            if num_alternatives == 0 {
-                self.grid[idx] = Cell::Collapsed(
-                    u8::try_from(val_idx + 1).expect("Value out of range"),
-                );
-                break;
-            }
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Couldn't read `{path}`: {e}"))?;
+    let puzzle = Sudoku::from_block_str(&text)
+        .map_err(|e| format!("Couldn't parse `{path}`: {e}"))?;
 
-            // Nothing in the same subsection can be the same
-            let subsection_idx = Self::subsection_idx(idx);
-            let (row, col) = Self::idx_to_coord(subsection_idx);
-            num_alternatives = 0;
-
-            for row in row..row + BOARD_SEP {
-                for col in col..col + BOARD_SEP {
-                    let tmp_idx = Self::coord_to_idx((row, col));
-
-                    if let &mut Cell::Superposition(ref mut s) =
-                        &mut self.grid[tmp_idx]
-                    {
-                        if tmp_idx != idx && s[val_idx] {
-                            num_alternatives += 1;
-                        }
-                    }
-                }
-            }
+    println!("Clues: {}", puzzle.clue_count());
 
-            if num_alternatives == 0 {
-                self.grid[idx] = Cell::Collapsed(
-                    u8::try_from(val_idx + 1).expect("Value out of range"),
-                );
-                break;
-            }
-        }
+    // A board with clashing clues has no solution, but searching for one can
+    // take a long time when there are few clues
+    if let Err(conflict) = puzzle.validate() {
+        println!("Valid: no ({conflict})");
+        println!("Solutions: none");
+        println!("Difficulty: unrated");
+        return Ok(());
     }
 
-    fn solve(&mut self) {
-        let mut iters_without_collapse = 0;
+    println!("Valid: yes");
 
-        while !self.is_solved() {
-            for idx in 0..BOARD_SIZE {
-                self.solve_pure_negative(idx);
-                self.propagate(idx);
-            }
+    let solutions = match puzzle.solution_class() {
+        SolutionClass::None => "none",
+        SolutionClass::Unique => "unique",
+        SolutionClass::Multiple => "multiple",
+    };
 
-            let mut collapsed = false;
+    println!("Solutions: {solutions}");
 
-            for idx in 0..BOARD_SIZE {
-                if matches!(self.grid[idx], Cell::Superposition(_)) {
-                    if let Some(value) = self.grid[idx].collapse() {
-                        self.grid[idx] = Cell::Collapsed(value);
-                        self.solve_pure_negative(idx);
-                        self.propagate(idx);
-                        collapsed = true;
-                    }
-                }
+    match puzzle.rate() {
+        Some(difficulty) => println!("Difficulty: {difficulty:?}"),
+        None => println!("Difficulty: unrated"),
+    }
 
-                if self.grid[idx].count_superstates().unwrap_or(1) == 0 {
-                    return;
-                }
-            }
+    Ok(())
+}
 
-            if collapsed {
-                iters_without_collapse = 0;
-            } else {
-                iters_without_collapse += 1;
-            }
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
 
-            if iters_without_collapse > 3 {
-                break;
-            }
+    let result = match args.first().map(String::as_str) {
+        None => {
+            run_benchmark();
+            Ok(())
         }
-
-        if !self.is_solved() {
-            // Backtrack
-            let idx = self
-                .grid
-                .iter()
-                .enumerate()
-                .position(|(_, cell)| matches!(cell, Cell::Superposition(_)))
-                .expect("No superstates found");
-
-            let Cell::Superposition(s) = self.grid[idx] else { unreachable!() };
-
-            for possible_val in s.iter().enumerate().filter_map(|(idx, val)| {
-                if *val { Some(idx + 1) } else { None }
-            }) {
-                let mut clone = *self;
-                clone.grid[idx] = Cell::Collapsed(
-                    u8::try_from(possible_val).expect("Value out of range"),
-                );
-
-                clone.solve();
-
-                if clone.is_solved() {
-                    *self = clone;
-                    return;
-                }
-            }
+        Some("generate") => generate(&args[1..]),
+        Some("rate") => rate(&args[1..]),
+        Some(command) => Err(format!("Unknown command `{command}`")),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            ExitCode::from(2)
         }
     }
 }
 
-impl std::fmt::Display for Sudoku {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (idx, cell) in self.grid.iter().enumerate() {
-            if idx % (BOARD_LEN * BOARD_SEP) == 0 {
-                if idx > 0 {
-                    writeln!(f, "|")?;
-                }
-
-                write!(f, "{ROW_SEP}\n| ")?;
-            } else if idx % BOARD_SEP == 0 {
-                write!(f, "| ")?;
-
-                if idx % BOARD_LEN == 0 {
-                    write!(f, "\n| ")?;
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
 
-            write!(f, "{cell} ")?;
-        }
+    use super::{SAMPLE_GRID, benchmark};
 
-        write!(f, "|\n{ROW_SEP}")?;
+    #[test]
+    fn benchmark_solves_at_least_once() {
+        let result = benchmark(&SAMPLE_GRID, Duration::ZERO).unwrap();
 
-        Ok(())
+        assert_eq!(result.iters, 1);
+        assert_eq!(result.average(), result.elapsed);
     }
-}
-
-// Easy grid
-// const EXAMPLE_GRID: [[u8; 9]; 9] = [
-//     [0, 0, 0, 2, 6, 0, 7, 0, 1],
-//     [6, 8, 0, 0, 7, 0, 0, 9, 0],
-//     [1, 9, 0, 0, 0, 4, 5, 0, 0],
-//     [8, 2, 0, 1, 0, 0, 0, 4, 0],
-//     [0, 0, 4, 6, 0, 2, 9, 0, 0],
-//     [0, 5, 0, 0, 0, 3, 0, 2, 8],
-//     [0, 0, 9, 3, 0, 0, 0, 7, 4],
-//     [0, 4, 0, 0, 5, 0, 0, 3, 6],
-//     [7, 0, 3, 0, 1, 8, 0, 0, 0],
-// ];
 
-// Izzy's grid
-// const SAMPLE_GRID: [[u8; 9]; 9] = [
-//     [4, 0, 7, 5, 0, 0, 0, 3, 0],
-//     [0, 1, 0, 0, 7, 0, 0, 0, 0],
-//     [0, 0, 0, 0, 0, 8, 0, 0, 0],
-//     [1, 0, 0, 2, 0, 6, 4, 0, 0],
-//     [0, 0, 0, 0, 0, 0, 0, 0, 7],
-//     [0, 8, 0, 3, 0, 0, 0, 1, 9],
-//     [0, 3, 0, 0, 5, 0, 0, 4, 8],
-//     [2, 0, 0, 0, 1, 0, 0, 9, 5],
-//     [0, 0, 0, 0, 0, 0, 0, 0, 6],
-// ];
+    #[test]
+    fn benchmark_reports_unsolvable_grids() {
+        let mut grid = SAMPLE_GRID;
+        grid[0][0] = 2;
 
-// Hard grid
-const SAMPLE_GRID: [[u8; 9]; 9] = [
-    [0, 2, 0, 0, 0, 0, 0, 0, 0],
-    [0, 0, 0, 6, 0, 0, 0, 0, 3],
-    [0, 7, 4, 0, 8, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0, 3, 0, 0, 2],
-    [0, 8, 0, 0, 4, 0, 0, 1, 0],
-    [6, 0, 0, 5, 0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 1, 0, 7, 8, 0],
-    [5, 0, 0, 0, 0, 9, 0, 0, 0],
-    [0, 0, 0, 0, 0, 0, 0, 4, 0],
-];
-
-fn main() {
-    let target_time = std::time::Duration::from_millis(5000);
-    let mut iters = 0;
-
-    let start = std::time::Instant::now();
-
-    while start.elapsed() < target_time {
-        let mut sudoku = Sudoku::from_zero_grid(&SAMPLE_GRID);
-        sudoku.initialize_superpositions();
-        sudoku.solve();
-
-        iters += 1;
+        assert!(benchmark(&grid, Duration::ZERO).is_err());
     }
-
-    println!("Elapsed: {:?}", start.elapsed());
-    println!("Average: {:?}", start.elapsed() / iters);
-
-    let mut sudoku = Sudoku::from_zero_grid(&SAMPLE_GRID);
-
-    println!("{sudoku}");
-
-    sudoku.initialize_superpositions();
-    sudoku.solve();
-
-    println!("{sudoku}");
 }
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use crate::{ParseError, Sudoku};
+
+/// Metadata read from the header of a puzzle file by
+/// [`Sudoku::from_sudoku_format`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PuzzleMeta {
+    /// The value of a `Title:` line
+    pub title: Option<String>,
+
+    /// The value of an `Author:` line
+    pub author: Option<String>,
+
+    /// The value of a `Source:` line
+    pub source: Option<String>,
+
+    /// The value of a `Difficulty:` line, as written. Files use their own
+    /// scales, so this isn't converted to a [`Difficulty`]
+    ///
+    /// [`Difficulty`]: crate::Difficulty
+    pub difficulty: Option<String>,
+
+    /// Every other `Key: value` line, by key as written
+    pub other: BTreeMap<String, String>,
+}
+
+impl Sudoku {
+    /// Parses a puzzle file whose grid is preceded by `Key: value` metadata
+    /// lines, e.g. `Author: ...` or `Difficulty: ...`. Keys are matched
+    /// without regard to case, and a repeated key keeps its last value.
+    /// Blank lines and comments starting with `#` or `//` are skipped.
+    /// Everything from the first other line without a `:` is read as a grid
+    /// by [`Sudoku::from_block_str`]
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Sudoku::from_block_str`] would on the grid
+    pub fn from_sudoku_format(
+        s: &str,
+    ) -> Result<(Self, PuzzleMeta), ParseError> {
+        let mut meta = PuzzleMeta::default();
+        let mut lines = s.lines().peekable();
+
+        while let Some(line) = lines.peek() {
+            let line = line.trim();
+
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("//")
+            {
+                lines.next();
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                break;
+            };
+
+            let (key, value) = (key.trim(), value.trim().to_string());
+
+            match key.to_ascii_lowercase().as_str() {
+                "title" => meta.title = Some(value),
+                "author" => meta.author = Some(value),
+                "source" => meta.source = Some(value),
+                "difficulty" => meta.difficulty = Some(value),
+                _ => {
+                    meta.other.insert(key.to_string(), value);
+                }
+            }
+
+            lines.next();
+        }
+
+        let grid = lines.collect::<Vec<_>>().join("\n");
+
+        Ok((Self::from_block_str(&grid)?, meta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Sudoku;
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    #[test]
+    fn comments_are_skipped_among_headers() {
+        let text = format!(
+            "# Saved from a magazine\nTitle: Hard\n// Note: solved by hand\nAuthor: A. Setter\n\n{HARD}"
+        );
+        let (puzzle, meta) = Sudoku::from_sudoku_format(&text).unwrap();
+
+        assert_eq!(meta.title.as_deref(), Some("Hard"));
+        assert_eq!(meta.author.as_deref(), Some("A. Setter"));
+        assert!(meta.other.is_empty());
+        assert!(puzzle.values_eq(&HARD.parse().unwrap()));
+    }
+
+    #[test]
+    fn difficulty_header_precedes_a_block_grid() {
+        let rows: Vec<&str> =
+            (0..9).map(|row| &HARD[row * 9..row * 9 + 9]).collect();
+        let text = format!(
+            "Author: A. Setter\nDifficulty: 4 stars\nRating: 7.2\n\n{}\n",
+            rows.join("\n")
+        );
+        let (puzzle, meta) = Sudoku::from_sudoku_format(&text).unwrap();
+
+        assert_eq!(meta.author.as_deref(), Some("A. Setter"));
+        assert_eq!(meta.difficulty.as_deref(), Some("4 stars"));
+        assert_eq!(meta.other.get("Rating").map(String::as_str), Some("7.2"));
+        assert!(puzzle.values_eq(&HARD.parse().unwrap()));
+    }
+}
@@ -0,0 +1,55 @@
+use std::time::{Duration, Instant};
+
+use crate::{Search, SolveError, Sudoku};
+
+/// Measurements taken during a single call to [`Sudoku::solve_timed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolveMetrics {
+    /// Wall-clock time spent solving
+    pub elapsed: Duration,
+
+    /// Number of deterministic passes made over the board, across every
+    /// branch of the search
+    pub passes: usize,
+}
+
+impl Sudoku {
+    /// Solves the board like [`Sudoku::solve`], also measuring how long it
+    /// took and how much work was done
+    pub fn solve_timed(&mut self) -> (Result<(), SolveError>, SolveMetrics) {
+        let mut ctx = Search::default();
+
+        let start = Instant::now();
+        let result = self.search_first(&mut ctx);
+        let elapsed = start.elapsed();
+
+        (result, SolveMetrics { elapsed, passes: ctx.passes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::Sudoku;
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    #[test]
+    fn timed_solve_measures_the_hard_grid() {
+        let mut board: Sudoku = HARD.parse().unwrap();
+        board.initialize_superpositions();
+
+        let (result, metrics) = board.solve_timed();
+
+        assert_eq!(result, Ok(()));
+        assert!(board.is_complete_and_correct());
+        assert!(metrics.elapsed > Duration::ZERO);
+        assert!(metrics.passes > 0);
+
+        // A board that is already solved needs no passes
+        let (result, metrics) = board.solve_timed();
+        assert_eq!(result, Ok(()));
+        assert_eq!(metrics.passes, 0);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{Cell, Sudoku};
+
+/// A constraint on whether a cell's value must be odd or even, as used by
+/// odd/even sudoku variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Parity {
+    Odd,
+    Even,
+}
+
+impl Parity {
+    /// Whether `value` satisfies the constraint
+    #[must_use]
+    pub const fn allows(self, value: u8) -> bool {
+        match self {
+            Self::Odd => !value.is_multiple_of(2),
+            Self::Even => value.is_multiple_of(2),
+        }
+    }
+}
+
+impl Sudoku {
+    /// The parity constraint on the cell at `(row, col)`, if any
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinate is off the board
+    #[must_use]
+    pub fn parity(&self, row: usize, col: usize) -> Option<Parity> {
+        self.parity_of(Self::checked_coord_to_idx((row, col)))
+    }
+
+    /// The parity constraint on the cell at `idx`, if any
+    pub(crate) const fn parity_of(&self, idx: usize) -> Option<Parity> {
+        if self.odd_cells & 1 << idx != 0 {
+            Some(Parity::Odd)
+        } else if self.even_cells & 1 << idx != 0 {
+            Some(Parity::Even)
+        } else {
+            None
+        }
+    }
+
+    /// Constrains the cell at `(row, col)` to odd or even values, or removes
+    /// the constraint if `parity` is `None`. Candidates of the wrong parity
+    /// are eliminated straight away
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinate is off the board
+    pub fn set_parity(
+        &mut self,
+        row: usize,
+        col: usize,
+        parity: Option<Parity>,
+    ) {
+        let idx = Self::checked_coord_to_idx((row, col));
+
+        self.odd_cells &= !(1 << idx);
+        self.even_cells &= !(1 << idx);
+
+        match parity {
+            Some(Parity::Odd) => self.odd_cells |= 1 << idx,
+            Some(Parity::Even) => self.even_cells |= 1 << idx,
+            None => {}
+        }
+
+        self.apply_parity(idx);
+    }
+
+    /// Removes candidates of the wrong parity from the cell at `idx`
+    pub(crate) fn apply_parity(&mut self, idx: usize) {
+        let Some(parity) = self.parity_of(idx) else {
+            return;
+        };
+
+        if let Cell::Superposition(ref mut s) = self.grid[idx] {
+            for (val_idx, candidate) in s.iter_mut().enumerate() {
+                let value =
+                    u8::try_from(val_idx + 1).expect("Value out of range");
+                *candidate &= parity.allows(value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Parity;
+    use crate::{Conflict, SolveError, Sudoku};
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    #[test]
+    fn clue_of_the_wrong_parity_is_invalid() {
+        let mut board: Sudoku = HARD.parse().unwrap();
+        board.set_parity(0, 1, Some(Parity::Odd));
+
+        assert_eq!(
+            board.validate(),
+            Err(Conflict::WrongParity {
+                cell: (0, 1),
+                value: 2,
+                parity: Parity::Odd
+            })
+        );
+
+        board.initialize_superpositions();
+        assert_eq!(board.solve(), Err(SolveError::AlreadyInvalid));
+    }
+
+    #[test]
+    fn solution_respects_parity() {
+        let mut solution: Sudoku = HARD.parse().unwrap();
+        solution.initialize_superpositions();
+        solution.solve().unwrap();
+        assert!(solution.is_complete_and_correct());
+
+        // Constrain (0, 0) to the parity of its solved value
+        let value = solution.grid[0].value().unwrap();
+        let parity =
+            if Parity::Even.allows(value) { Parity::Even } else { Parity::Odd };
+
+        let mut board: Sudoku = HARD.parse().unwrap();
+        board.set_parity(0, 0, Some(parity));
+        board.initialize_superpositions();
+        board.solve().unwrap();
+
+        assert!(board.is_complete_and_correct());
+        assert!(parity.allows(board.grid[0].value().unwrap()));
+
+        solution.set_parity(
+            0,
+            0,
+            Some(match parity {
+                Parity::Odd => Parity::Even,
+                Parity::Even => Parity::Odd,
+            }),
+        );
+        assert!(!solution.is_complete_and_correct());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    BOARD_LEN, Cell, Conflict, Search, SolveError, SolverConfig, Sudoku,
+    UnitKind,
+};
+
+/// Everything [`Sudoku::solve_diagnostics`] found out about a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveReport {
+    /// The first repeated value, or value of the wrong parity, among the
+    /// filled cells, if any
+    pub conflict: Option<Conflict>,
+
+    /// Every unit whose blanks can't hold all of its missing values, as
+    /// described for [`Conflict::Unsatisfiable`]. Only checked when there's
+    /// no repeated value
+    pub unsatisfiable_units: Vec<(UnitKind, usize)>,
+
+    /// The `(row, col)` of the first cell left with no candidates by
+    /// deduction, before any guessing
+    pub emptied_cell: Option<(usize, usize)>,
+
+    /// Why solving failed, if it did
+    pub error: Option<SolveError>,
+
+    /// Whether the board was solved
+    pub solved: bool,
+}
+
+impl Sudoku {
+    /// Validates the puzzle, checks every unit can still be completed, looks
+    /// for a cell that deduction empties and finally solves the board, all in
+    /// one report. This is for explaining what is wrong with a broken puzzle;
+    /// on success the board is left solved as by [`Sudoku::solve_into`]
+    pub fn solve_diagnostics(&mut self) -> SolveReport {
+        let conflict = self.validate().err();
+
+        let mut propagated = *self;
+        propagated.initialize_superpositions();
+        propagated.propagate_all();
+
+        let unsatisfiable_units = if conflict.is_none() {
+            [UnitKind::Row, UnitKind::Column, UnitKind::Box]
+                .into_iter()
+                .flat_map(|kind| (0..BOARD_LEN).map(move |index| (kind, index)))
+                .filter(|&(kind, index)| {
+                    !propagated.unit_satisfiable(kind, index)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let config =
+            SolverConfig { backtracking: false, ..SolverConfig::new() };
+        let mut deduced = propagated;
+
+        let emptied_cell = if deduced
+            .deduce(&mut Search { config: &config, ..Search::default() })
+        {
+            None
+        } else {
+            deduced
+                .grid
+                .iter()
+                .position(|cell| {
+                    matches!(cell, Cell::Superposition(_))
+                        && cell.candidate_mask() == 0
+                })
+                .map(Self::idx_to_coord)
+        };
+
+        self.initialize_superpositions();
+        let error = self.solve().err();
+
+        SolveReport {
+            conflict,
+            unsatisfiable_units,
+            emptied_cell,
+            error,
+            solved: error.is_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Conflict, SolveError, Sudoku, UnitKind};
+
+    #[test]
+    fn contradictory_puzzle_reports_where_it_breaks() {
+        // Row 1 leaves only 9 for R1C9, but column 9 already holds a 9
+        let mut board: Sudoku =
+            format!("12345678.{}9{}", ".".repeat(44), ".".repeat(27))
+                .parse()
+                .unwrap();
+
+        let report = board.solve_diagnostics();
+
+        // The row, the column and the subsection all run out of room
+        assert_eq!(report.conflict, None);
+        assert_eq!(
+            report.unsatisfiable_units,
+            [(UnitKind::Row, 0), (UnitKind::Column, 8), (UnitKind::Box, 2)]
+        );
+        assert_eq!(report.emptied_cell, Some((0, 8)));
+        assert_eq!(report.error, Some(SolveError::NoSolution));
+        assert!(!report.solved);
+    }
+
+    #[test]
+    fn repeated_value_is_named_in_the_report() {
+        let mut board: Sudoku =
+            format!("1.1{}", ".".repeat(78)).parse().unwrap();
+
+        let report = board.solve_diagnostics();
+
+        assert_eq!(
+            report.conflict,
+            Some(Conflict::Duplicate {
+                first: (0, 0),
+                second: (0, 2),
+                value: 1
+            })
+        );
+        assert!(report.unsatisfiable_units.is_empty());
+        assert_eq!(report.error, Some(SolveError::AlreadyInvalid));
+        assert!(!report.solved);
+    }
+}
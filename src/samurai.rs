@@ -0,0 +1,327 @@
+use crate::{
+    BOARD_LEN, BOARD_SEP, BOARD_SIZE, Cell, Conflict, Search, SolveError,
+    Sudoku,
+};
+
+impl Sudoku {
+    /// Checks that `self` and `other` agree on every cell in `shared_cells`,
+    /// given as `(row, col)` coordinates valid on both boards. Cells that are
+    /// empty on either board are compatible with anything. This is the
+    /// building block for composite layouts where grids overlap
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Conflict::Mismatch`] for the first shared cell filled with
+    /// different values on the two boards
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate is off the board
+    pub fn overlay(
+        &self,
+        other: &Self,
+        shared_cells: &[(usize, usize)],
+    ) -> Result<(), Conflict> {
+        for &cell in shared_cells {
+            let idx = Self::checked_coord_to_idx(cell);
+
+            if let (Some(ours), Some(theirs)) =
+                (self.grid[idx].value(), other.grid[idx].value())
+                && ours != theirs
+            {
+                return Err(Conflict::Mismatch { cell, ours, theirs });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Five grids in the samurai layout, one in each corner and one in the centre
+///
+/// Each corner subsection of the centre grid is shared with the subsection of
+/// the corner grid closest to it. The grids are stored top left, top right,
+/// centre, bottom left, bottom right
+#[derive(Debug, Clone, Copy)]
+pub struct SamuraiSudoku {
+    pub grids: [Sudoku; 5],
+}
+
+impl SamuraiSudoku {
+    /// Index of the centre grid in [`SamuraiSudoku::grids`]
+    pub const CENTRE: usize = 2;
+
+    /// Each corner grid with the index of the top left cell of its shared
+    /// subsection, and of the same cell in the centre grid
+    const OVERLAPS: [(usize, usize, usize); 4] = [
+        (0, Sudoku::coord_to_idx((6, 6)), Sudoku::coord_to_idx((0, 0))),
+        (1, Sudoku::coord_to_idx((6, 0)), Sudoku::coord_to_idx((0, 6))),
+        (3, Sudoku::coord_to_idx((0, 6)), Sudoku::coord_to_idx((6, 0))),
+        (4, Sudoku::coord_to_idx((0, 0)), Sudoku::coord_to_idx((6, 6))),
+    ];
+
+    #[must_use]
+    pub const fn new(grids: [Sudoku; 5]) -> Self {
+        Self { grids }
+    }
+
+    /// Every shared cell as `(corner grid, index there, index in the centre)`
+    fn shared_cells() -> impl Iterator<Item = (usize, usize, usize)> {
+        Self::OVERLAPS.into_iter().flat_map(|(grid, corner, centre)| {
+            (0..BOARD_LEN).map(move |offset| {
+                let offset = Sudoku::coord_to_idx((
+                    offset / BOARD_SEP,
+                    offset % BOARD_SEP,
+                ));
+
+                (grid, corner + offset, centre + offset)
+            })
+        })
+    }
+
+    /// Checks every grid with [`Sudoku::validate`] and that each shared cell
+    /// holds the same value on both grids it belongs to
+    ///
+    /// # Errors
+    ///
+    /// Returns the first conflict found, with coordinates of shared cells
+    /// given on the corner grid
+    pub fn validate(&self) -> Result<(), Conflict> {
+        for grid in &self.grids {
+            grid.validate()?;
+        }
+
+        for (grid, idx, centre_idx) in Self::shared_cells() {
+            if let (Some(ours), Some(theirs)) = (
+                self.grids[grid].grid[idx].value(),
+                self.grids[Self::CENTRE].grid[centre_idx].value(),
+            ) && ours != theirs
+            {
+                return Err(Conflict::Mismatch {
+                    cell: Sudoku::idx_to_coord(idx),
+                    ours,
+                    theirs,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether every grid is completely and correctly filled and the grids
+    /// agree on their shared cells
+    #[must_use]
+    pub fn is_complete_and_correct(&self) -> bool {
+        self.grids.iter().all(Sudoku::is_solved) && self.validate().is_ok()
+    }
+
+    /// Solves all five grids together. Each grid is deduced as far as it will
+    /// go on its own, then the candidates of every shared cell are narrowed to
+    /// those allowed on both grids, repeating until nothing changes. Guesses
+    /// are made only once that stalls. Candidates are initialized here, so
+    /// there's no need to call [`Sudoku::initialize_superpositions`] first
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::AlreadyInvalid`] if the grids already conflict,
+    /// or [`SolveError::NoSolution`] if they can't be completed together
+    pub fn solve(&mut self) -> Result<(), SolveError> {
+        if self.validate().is_err() {
+            return Err(SolveError::AlreadyInvalid);
+        }
+
+        let mut board = *self;
+
+        for grid in &mut board.grids {
+            grid.initialize_superpositions();
+        }
+
+        *self = board.search().ok_or(SolveError::NoSolution)?;
+        Ok(())
+    }
+
+    /// Deduces and guesses until every grid is solved, returning `None` if
+    /// the current state is a dead end
+    fn search(mut self) -> Option<Self> {
+        if !self.deduce() {
+            return None;
+        }
+
+        if self.grids.iter().all(Sudoku::is_solved) {
+            return self.is_complete_and_correct().then_some(self);
+        }
+
+        // Guess in the cell with the fewest candidates on any grid
+        let (grid, idx, _) = (0..self.grids.len())
+            .flat_map(|grid| (0..BOARD_SIZE).map(move |idx| (grid, idx)))
+            .filter_map(|(grid, idx)| {
+                self.grids[grid].grid[idx]
+                    .count_superstates()
+                    .map(|count| (grid, idx, count))
+            })
+            .min_by_key(|&(_, _, count)| count)?;
+
+        let mask = self.grids[grid].grid[idx].candidate_mask();
+
+        (1..=9u8).filter(|value| mask & 1 << (value - 1) != 0).find_map(
+            |value| {
+                let mut clone = self;
+                clone.grids[grid].grid[idx] = Cell::Collapsed(value);
+                clone.search()
+            },
+        )
+    }
+
+    /// Runs the deterministic solver on each grid and shares the results
+    /// across the overlaps until nothing changes. Returns `false` on a
+    /// contradiction
+    fn deduce(&mut self) -> bool {
+        loop {
+            for grid in &mut self.grids {
+                if !grid.deduce(&mut Search::default()) {
+                    return false;
+                }
+            }
+
+            match self.sync_shared_cells() {
+                Some(true) => {}
+                Some(false) => return true,
+                None => return false,
+            }
+        }
+    }
+
+    /// Narrows both copies of every shared cell to the candidates allowed on
+    /// each. Returns whether anything changed, or `None` if a shared cell has
+    /// no value that works on both grids
+    fn sync_shared_cells(&mut self) -> Option<bool> {
+        let mut changed = false;
+
+        for (grid, idx, centre_idx) in Self::shared_cells() {
+            let corner = self.grids[grid].grid[idx];
+            let centre = self.grids[Self::CENTRE].grid[centre_idx];
+
+            let mask = corner.candidate_mask() & centre.candidate_mask();
+
+            if mask == 0 {
+                return None;
+            }
+
+            let narrow = |cell: Cell| match cell {
+                Cell::Fixed(_) | Cell::Collapsed(_) => cell,
+                _ if cell.candidate_mask() == mask => cell,
+                Cell::Empty | Cell::Superposition(_) => {
+                    Cell::from_candidate_mask(mask)
+                }
+            };
+
+            let (corner_after, centre_after) = (narrow(corner), narrow(centre));
+            changed |= corner_after != corner || centre_after != centre;
+
+            self.grids[grid].grid[idx] = corner_after;
+            self.grids[Self::CENTRE].grid[centre_idx] = centre_after;
+        }
+
+        Some(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SamuraiSudoku;
+    use crate::{BOARD_SIZE, Conflict, Sudoku};
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    /// Swaps the top and bottom bands and the left and right stacks, moving
+    /// each corner subsection to the opposite corner
+    fn swap_corners(sudoku: &Sudoku) -> Sudoku {
+        let flip = |n: usize| match n {
+            0..3 => n + 6,
+            6.. => n - 6,
+            _ => n,
+        };
+
+        let mut flipped = *sudoku;
+
+        for idx in 0..BOARD_SIZE {
+            let (row, col) = Sudoku::idx_to_coord(idx);
+            flipped.grid[Sudoku::coord_to_idx((flip(row), flip(col)))] =
+                sudoku.grid[idx];
+        }
+
+        flipped
+    }
+
+    fn solved_samurai() -> SamuraiSudoku {
+        let mut centre: Sudoku = HARD.parse().unwrap();
+        centre.initialize_superpositions();
+        centre.solve().unwrap();
+
+        let corner = swap_corners(&centre);
+        SamuraiSudoku::new([corner, corner, centre, corner, corner])
+    }
+
+    #[test]
+    fn solves_to_five_valid_grids() {
+        let solution = solved_samurai();
+        assert!(solution.is_complete_and_correct());
+
+        // Every other cell, which keeps each shared cell a clue on both of
+        // its grids or on neither
+        let keep: Vec<_> =
+            (0..BOARD_SIZE).step_by(2).map(Sudoku::idx_to_coord).collect();
+        let puzzle = SamuraiSudoku::new(
+            solution.grids.map(|grid| grid.mask_solution(&keep)),
+        );
+        assert!(puzzle.validate().is_ok());
+
+        let mut samurai = puzzle;
+        samurai.solve().unwrap();
+
+        assert!(samurai.is_complete_and_correct());
+
+        for (grid, clues) in samurai.grids.iter().zip(&puzzle.grids) {
+            assert!(grid.is_complete_and_correct());
+            assert!(clues.is_subset_of(grid));
+        }
+    }
+
+    #[test]
+    fn rejects_disagreeing_shared_cells() {
+        let mut samurai = solved_samurai();
+
+        // R1C1 of the centre grid is R7C7 of the top left grid
+        let centre = samurai.grids[SamuraiSudoku::CENTRE].grid[0];
+        let other = samurai.grids[SamuraiSudoku::CENTRE].grid[1];
+        samurai.grids[0].grid[Sudoku::coord_to_idx((6, 6))] = other;
+
+        assert_ne!(centre, other);
+        assert!(samurai.validate().is_err());
+    }
+
+    #[test]
+    fn overlay_checks_shared_cells() {
+        let puzzle: Sudoku = HARD.parse().unwrap();
+        let solution = solved_samurai().grids[SamuraiSudoku::CENTRE];
+        let every_cell: Vec<_> =
+            (0..BOARD_SIZE).map(Sudoku::idx_to_coord).collect();
+
+        // The clues agree with the solution, and the blanks with anything
+        assert_eq!(puzzle.overlay(&solution, &every_cell), Ok(()));
+        assert_eq!(solution.overlay(&Sudoku::empty(), &every_cell), Ok(()));
+
+        let flipped = swap_corners(&solution);
+        let (ours, theirs) = (solution.grid[0], flipped.grid[0]);
+        assert_ne!(ours, theirs);
+
+        assert_eq!(
+            solution.overlay(&flipped, &[(4, 4), (0, 0)]),
+            Err(Conflict::Mismatch {
+                cell: (0, 0),
+                ours: ours.value().unwrap(),
+                theirs: theirs.value().unwrap(),
+            })
+        );
+    }
+}
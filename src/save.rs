@@ -0,0 +1,199 @@
+use crate::{BOARD_LEN, BOARD_SIZE, Cell, ParseError, Sudoku};
+
+/// Hex digits used for each cell's candidate mask in a save string
+const MASK_DIGITS: usize = 3;
+
+impl Sudoku {
+    /// Encodes the full state of the board, including solver placements and
+    /// the candidates of every unfilled cell, as three lines of text:
+    ///
+    /// 1. The clues, with `.` for every other cell
+    /// 2. The values filled in by the solver, with `.` for every other cell
+    /// 3. Three hex digits per cell giving its candidates as a bitmask, or
+    ///    `...` for cells without a superposition yet. Filled cells are `000`
+    ///
+    /// Parity constraints aren't included
+    #[must_use]
+    pub fn to_save_string(&self) -> String {
+        let values = |keep: fn(&Cell) -> Option<u8>| -> String {
+            self.grid
+                .iter()
+                .map(|cell| keep(cell).map_or('.', |n| char::from(b'0' + n)))
+                .collect()
+        };
+
+        let givens = values(|cell| match cell {
+            Cell::Fixed(n) => Some(*n),
+            _ => None,
+        });
+
+        let placements = values(|cell| match cell {
+            Cell::Collapsed(n) => Some(*n),
+            _ => None,
+        });
+
+        let candidates: String = self
+            .grid
+            .iter()
+            .map(|cell| match cell {
+                Cell::Empty => "...".to_string(),
+                Cell::Fixed(_) | Cell::Collapsed(_) => "000".to_string(),
+                Cell::Superposition(_) => {
+                    format!("{:03x}", cell.candidate_mask())
+                }
+            })
+            .collect();
+
+        format!("{givens}\n{placements}\n{candidates}")
+    }
+
+    /// Reads a board written by [`Sudoku::to_save_string`]. Positions in
+    /// errors are character offsets into the whole string
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::WrongLength`] if there aren't three lines of the
+    /// expected lengths and [`ParseError::InvalidChar`] for an unexpected
+    /// character, including a cell that is both a clue and a placement
+    pub fn from_save_string(s: &str) -> Result<Self, ParseError> {
+        let lines: Vec<&str> = s.trim_end().lines().collect();
+
+        let [givens, placements, candidates] = lines[..] else {
+            return Err(ParseError::WrongLength {
+                found: lines.len(),
+                expected: 3,
+            });
+        };
+
+        let line_len = |line: &str, expected| {
+            let found = line.chars().count();
+
+            if found == expected {
+                Ok(())
+            } else {
+                Err(ParseError::WrongLength { found, expected })
+            }
+        };
+
+        line_len(givens, BOARD_SIZE)?;
+        line_len(placements, BOARD_SIZE)?;
+        line_len(candidates, BOARD_SIZE * MASK_DIGITS)?;
+
+        let placements_start = BOARD_SIZE + 1;
+        let candidates_start = placements_start + BOARD_SIZE + 1;
+
+        if let Some(offset) = candidates.chars().position(|ch| !ch.is_ascii()) {
+            return Err(ParseError::InvalidChar {
+                pos: candidates_start + offset,
+                ch: candidates.chars().nth(offset).unwrap_or_default(),
+            });
+        }
+
+        let mut sudoku = Self::default();
+
+        let groups = candidates.as_bytes().chunks(MASK_DIGITS);
+
+        for (idx, ((given_ch, placed_ch), mask)) in
+            givens.chars().zip(placements.chars()).zip(groups).enumerate()
+        {
+            let digit = |ch: char, pos| match ch {
+                '.' => Ok(None),
+                '1'..='9' => Ok(Some(ch as u8 - b'0')),
+                _ => Err(ParseError::InvalidChar { pos, ch }),
+            };
+
+            let given = digit(given_ch, idx)?;
+            let placed = digit(placed_ch, placements_start + idx)?;
+
+            let mask_pos = candidates_start + idx * MASK_DIGITS;
+
+            sudoku.grid[idx] = match (given, placed) {
+                (Some(_), Some(_)) => {
+                    return Err(ParseError::InvalidChar {
+                        pos: placements_start + idx,
+                        ch: placed_ch,
+                    });
+                }
+                (Some(n), None) => Cell::Fixed(n),
+                (None, Some(n)) => Cell::Collapsed(n),
+                (None, None) if mask == b"..." => Cell::Empty,
+                (None, None) => {
+                    Cell::from_candidate_mask(parse_mask(mask, mask_pos)?)
+                }
+            };
+        }
+
+        Ok(sudoku)
+    }
+}
+
+/// Parses a cell's hex candidate mask, where `pos` is the offset of its first
+/// digit
+fn parse_mask(digits: &[u8], pos: usize) -> Result<u16, ParseError> {
+    let mut mask = 0;
+
+    for (offset, &byte) in digits.iter().enumerate() {
+        let ch = char::from(byte);
+        let invalid = ParseError::InvalidChar { pos: pos + offset, ch };
+
+        let digit = ch.to_digit(16).ok_or(invalid)?;
+        mask = mask << 4 | u16::try_from(digit).expect("Hex digits fit in u16");
+
+        if mask >= 1 << BOARD_LEN {
+            return Err(invalid);
+        }
+    }
+
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, ParseError, Sudoku};
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    /// The hard puzzle as far as logic alone takes it, holding clues, solver
+    /// placements and cells with candidates left
+    fn mid_solve() -> Sudoku {
+        let mut board: Sudoku = HARD.parse().unwrap();
+        board.initialize_superpositions();
+        assert!(!board.try_solve_logic_only());
+        board
+    }
+
+    #[test]
+    fn mid_solve_board_round_trips() {
+        let mut board = mid_solve();
+        board.set(8, 0, 0);
+
+        for kind in [
+            |cell: &Cell| matches!(cell, Cell::Fixed(_)),
+            |cell: &Cell| matches!(cell, Cell::Collapsed(_)),
+            |cell: &Cell| matches!(cell, Cell::Superposition(_)),
+            |cell: &Cell| matches!(cell, Cell::Empty),
+        ] {
+            assert!(board.grid.iter().any(kind));
+        }
+
+        let restored =
+            Sudoku::from_save_string(&board.to_save_string()).unwrap();
+        assert_eq!(restored.grid, board.grid);
+    }
+
+    #[test]
+    fn cell_that_is_both_a_clue_and_a_placement_is_rejected() {
+        let save = mid_solve().to_save_string();
+
+        // Give the first cell the solver filled a clue as well
+        let mut lines: Vec<String> = save.lines().map(String::from).collect();
+        let (idx, placement) =
+            lines[1].char_indices().find(|&(_, ch)| ch != '.').unwrap();
+        lines[0].replace_range(idx..=idx, &placement.to_string());
+
+        assert_eq!(
+            Sudoku::from_save_string(&lines.join("\n")).unwrap_err(),
+            ParseError::InvalidChar { pos: 82 + idx, ch: placement }
+        );
+    }
+}
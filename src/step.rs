@@ -0,0 +1,168 @@
+use std::fmt;
+
+use crate::{Cell, Sudoku, Technique};
+
+/// A single deduction made while solving, as returned by
+/// [`Sudoku::solve_steps`]. Coordinates are zero-indexed
+///
+/// [`Sudoku::solve_steps`]: crate::Sudoku::solve_steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub technique: Technique,
+    pub row: usize,
+    pub col: usize,
+
+    /// The value placed in the cell, or `None` if the step only removed
+    /// candidates
+    pub value: Option<u8>,
+
+    /// The candidates removed from the cell as a bitmask, where bit `n - 1`
+    /// stands for `n`. For placements this is every other candidate the cell
+    /// had
+    pub eliminated: u16,
+}
+
+impl Sudoku {
+    /// Replays a step recorded by [`Sudoku::solve_steps`], placing its value
+    /// or removing its eliminated candidates. Applying every step of a solve
+    /// in order to the initialized puzzle reproduces the solution
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step's coordinate is off the board
+    pub fn apply_step(&mut self, step: &Step) {
+        let idx = Self::checked_coord_to_idx((step.row, step.col));
+
+        if let Some(value) = step.value {
+            self.grid[idx] = Cell::Collapsed(value);
+        } else {
+            self.eliminate_mask(idx, step.eliminated);
+        }
+    }
+
+    /// Undoes [`Sudoku::apply_step`], restoring the candidates the cell had
+    /// when the solver made the step. Candidates removed by propagation in
+    /// between aren't recorded, so only the board's values are guaranteed to
+    /// match their state before the step. Steps must be reverted in the
+    /// reverse of the order they were applied
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step's coordinate is off the board
+    pub fn revert_step(&mut self, step: &Step) {
+        let idx = Self::checked_coord_to_idx((step.row, step.col));
+
+        let before = match (step.value, self.grid[idx]) {
+            (Some(value), _) => step.eliminated | 1 << (value - 1),
+            (None, Cell::Superposition(_)) => {
+                self.grid[idx].candidate_mask() | step.eliminated
+            }
+            (None, _) => return,
+        };
+
+        self.grid[idx] = Cell::from_candidate_mask(before);
+    }
+}
+
+impl fmt::Display for Step {
+    /// Describes the step in a sentence without a full stop, e.g. `R1C2 must
+    /// be 3 (hidden single)` or `R4C4 eliminated 5, 7 (pointing)`. Rows and
+    /// columns are numbered from one
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (row, col) = (self.row + 1, self.col + 1);
+
+        match (self.value, self.technique) {
+            (Some(value), Technique::Guess) => {
+                write!(f, "R{row}C{col} guessed as {value}")
+            }
+            (Some(value), technique) => {
+                write!(f, "R{row}C{col} must be {value} ({technique})")
+            }
+            (None, technique) => {
+                let values = (1..=9u8)
+                    .filter(|n| self.eliminated & 1 << (n - 1) != 0)
+                    .map(|n| n.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "R{row}C{col} eliminated {values} ({technique})")
+            }
+        }
+    }
+}
+
+impl Sudoku {
+    /// Solves the board like [`Sudoku::solve_steps`] and describes the path
+    /// to the solution, one sentence per line in the form of
+    /// [`Step`]'s `Display`. If solving fails, the reason is given instead
+    pub fn solve_explain(&mut self) -> String {
+        match self.solve_steps() {
+            Ok(steps) => steps
+                .iter()
+                .map(|step| format!("{step}."))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Err(e) => format!("Couldn't solve: {e}."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Sudoku;
+
+    const EASY: &str = "000260701680070090190004500820100040004602900050003028009300074040050036703018000";
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    #[test]
+    fn replaying_the_steps_reproduces_the_solution() {
+        let mut puzzle: Sudoku = HARD.parse().unwrap();
+        puzzle.initialize_superpositions();
+
+        let mut solution = puzzle;
+        let steps = solution.solve_steps().unwrap();
+
+        let mut board = puzzle;
+
+        for step in &steps {
+            board.apply_step(step);
+        }
+
+        assert!(board.is_complete_and_correct());
+        assert_eq!(board.grid, solution.grid);
+
+        for step in steps.iter().rev() {
+            board.revert_step(step);
+        }
+
+        assert!(board.values_eq(&puzzle));
+    }
+
+    #[test]
+    fn explanation_names_a_technique_for_every_step() {
+        let mut puzzle: Sudoku = EASY.parse().unwrap();
+        puzzle.initialize_superpositions();
+
+        let (mut stepped, mut explained) = (puzzle, puzzle);
+        let steps = stepped.solve_steps().unwrap();
+        let explanation = explained.solve_explain();
+
+        assert!(!explanation.is_empty());
+        assert!(explanation.contains("must be"));
+        assert!(explanation.contains("(hidden single)"));
+        assert_eq!(explanation.lines().count(), steps.len());
+        assert!(explanation.lines().all(|line| line.ends_with(").")));
+    }
+
+    #[test]
+    fn explanation_of_a_failed_solve_gives_the_reason() {
+        let mut board: Sudoku =
+            format!("11{}", ".".repeat(79)).parse().unwrap();
+
+        assert_eq!(
+            board.solve_explain(),
+            "Couldn't solve: the puzzle already breaks one of its constraints."
+        );
+    }
+}
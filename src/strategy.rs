@@ -0,0 +1,100 @@
+use crate::{Search, Sudoku};
+
+/// A solving technique that can be run alongside the built-in ones by
+/// [`Sudoku::solve_with_strategies`]. Every [`Technique`] is one
+///
+/// [`Technique`]: crate::Technique
+pub trait Strategy {
+    /// Makes whatever progress the strategy can on the board, returning
+    /// whether anything changed. The solver decides for itself whether the
+    /// board changed, so a wrong answer can't stop it from finishing.
+    /// Candidates have had every filled value removed from their peers when
+    /// this is called
+    fn apply(&self, sudoku: &mut Sudoku) -> bool;
+}
+
+impl Sudoku {
+    /// Runs the search's custom strategies in order, stopping at the first
+    /// one that changes the board. Returns whether any did
+    pub(crate) fn apply_strategies(&mut self, ctx: &Search<'_>) -> bool {
+        if ctx.strategies.is_empty() {
+            return false;
+        }
+
+        self.propagate_all();
+
+        ctx.strategies.iter().any(|strategy| {
+            let before = self.grid;
+            strategy.apply(self);
+            self.grid != before
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Strategy;
+    use crate::{Sudoku, Technique};
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    /// Does nothing, but claims to have made progress
+    struct Lying;
+
+    impl Strategy for Lying {
+        fn apply(&self, _: &mut Sudoku) -> bool {
+            true
+        }
+    }
+
+    /// Places a 5 in the top left cell if it's empty
+    struct FiveInCorner;
+
+    impl Strategy for FiveInCorner {
+        fn apply(&self, sudoku: &mut Sudoku) -> bool {
+            if sudoku.grid[0].value().is_some() {
+                return false;
+            }
+
+            sudoku.set(0, 0, 5);
+            true
+        }
+    }
+
+    fn initialized(line: &str) -> Sudoku {
+        let mut board: Sudoku = line.parse().unwrap();
+        board.initialize_superpositions();
+        board
+    }
+
+    #[test]
+    fn no_op_strategy_changes_nothing() {
+        let mut expected = initialized(HARD);
+        expected.solve().unwrap();
+
+        let mut board = initialized(HARD);
+        board.solve_with_strategies(&[Box::new(Lying)]).unwrap();
+
+        assert!(board.values_eq(&expected));
+    }
+
+    #[test]
+    fn custom_strategy_takes_effect() {
+        let mut board = Sudoku::empty();
+        board.initialize_superpositions();
+        board.solve_with_strategies(&[Box::new(FiveInCorner)]).unwrap();
+
+        assert!(board.is_complete_and_correct());
+        assert_eq!(board.grid[0].value(), Some(5));
+    }
+
+    #[test]
+    fn hidden_single_propagates_first() {
+        let mut board: Sudoku =
+            format!("12345678{}", ".".repeat(73)).parse().unwrap();
+        board.initialize_superpositions();
+
+        assert!(Technique::HiddenSingle.apply(&mut board));
+        assert_eq!(board.grid[8].value(), Some(9));
+    }
+}
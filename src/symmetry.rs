@@ -0,0 +1,358 @@
+use crate::{BOARD_LEN, BOARD_SEP, BOARD_SIZE, Cell, Layout, Sudoku};
+
+/// A transformation of the board that a puzzle's clue layout may be
+/// unchanged by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// A half turn about the centre cell
+    Rotational180,
+
+    /// A quarter turn about the centre cell
+    Rotational90,
+
+    /// Reflection in the middle row, swapping top and bottom
+    HorizontalMirror,
+
+    /// Reflection in the middle column, swapping left and right
+    VerticalMirror,
+
+    /// Reflection in the diagonal from the top left to the bottom right
+    MainDiagonal,
+
+    /// Reflection in the diagonal from the top right to the bottom left
+    AntiDiagonal,
+}
+
+impl Symmetry {
+    pub const ALL: [Self; 6] = [
+        Self::Rotational180,
+        Self::Rotational90,
+        Self::HorizontalMirror,
+        Self::VerticalMirror,
+        Self::MainDiagonal,
+        Self::AntiDiagonal,
+    ];
+
+    /// Where the symmetry moves the cell at `(row, col)`
+    const fn apply(self, (row, col): (usize, usize)) -> (usize, usize) {
+        let last = BOARD_LEN - 1;
+
+        match self {
+            Self::Rotational180 => (last - row, last - col),
+            Self::Rotational90 => (col, last - row),
+            Self::HorizontalMirror => (last - row, col),
+            Self::VerticalMirror => (row, last - col),
+            Self::MainDiagonal => (col, row),
+            Self::AntiDiagonal => (last - col, last - row),
+        }
+    }
+}
+
+impl Sudoku {
+    /// The symmetries under which the positions of the clues are unchanged.
+    /// Only which cells are clues matters, not their values
+    #[must_use]
+    pub fn clue_symmetry(&self) -> Vec<Symmetry> {
+        let is_clue = |idx: usize| matches!(self.grid[idx], Cell::Fixed(_));
+
+        Symmetry::ALL
+            .into_iter()
+            .filter(|symmetry| {
+                (0..BOARD_SIZE).all(|idx| {
+                    let moved = symmetry.apply(Self::idx_to_coord(idx));
+                    is_clue(idx) == is_clue(Self::coord_to_idx(moved))
+                })
+            })
+            .collect()
+    }
+}
+
+impl Sudoku {
+    /// Relabels every digit, replacing `n` with `permutation[n - 1]` in filled
+    /// cells and candidates alike. The result is an equivalent puzzle whose
+    /// solution is relabeled the same way
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permutation` doesn't contain each of 1 to 9 exactly once
+    pub fn apply_permutation(&mut self, permutation: [u8; BOARD_LEN]) {
+        let seen = permutation.iter().fold(0u16, |mask, &value| {
+            assert!((1..=9).contains(&value), "Permutation value out of range");
+            mask | 1 << (value - 1)
+        });
+
+        assert!(seen == (1 << BOARD_LEN) - 1, "Permutation repeats a value");
+
+        let relabel = |n: u8| permutation[n as usize - 1];
+
+        for cell in &mut self.grid {
+            *cell = match *cell {
+                Cell::Empty => Cell::Empty,
+                Cell::Fixed(n) => Cell::Fixed(relabel(n)),
+                Cell::Collapsed(n) => Cell::Collapsed(relabel(n)),
+                Cell::Superposition(s) => {
+                    let mut relabeled = [false; BOARD_LEN];
+
+                    for (val_idx, &possible) in s.iter().enumerate() {
+                        relabeled[permutation[val_idx] as usize - 1] = possible;
+                    }
+
+                    Cell::Superposition(relabeled)
+                }
+            };
+        }
+    }
+}
+
+/// Number of cells in a band or stack
+const BAND_SIZE: usize = BOARD_SEP * BOARD_LEN;
+
+impl Sudoku {
+    /// The cells of the three bands of three rows, top to bottom, each in
+    /// row-major order
+    #[must_use]
+    pub fn bands(&self) -> [[Cell; BAND_SIZE]; BOARD_SEP] {
+        std::array::from_fn(|band| {
+            std::array::from_fn(|i| self.grid[band * BAND_SIZE + i])
+        })
+    }
+
+    /// The cells of the three stacks of three columns, left to right, each in
+    /// row-major order
+    #[must_use]
+    pub fn stacks(&self) -> [[Cell; BAND_SIZE]; BOARD_SEP] {
+        std::array::from_fn(|stack| {
+            std::array::from_fn(|i| {
+                let (row, col) = (i / BOARD_SEP, i % BOARD_SEP);
+                self.grid[Self::coord_to_idx((row, stack * BOARD_SEP + col))]
+            })
+        })
+    }
+
+    /// Swaps bands `a` and `b`, keeping the order of the rows in each. Like
+    /// every swap here, this maps solutions to solutions
+    ///
+    /// # Panics
+    ///
+    /// Panics if either band index is greater than 2
+    pub fn swap_bands(&mut self, a: usize, b: usize) {
+        assert!(a < BOARD_SEP && b < BOARD_SEP, "Band index out of range");
+
+        for offset in 0..BOARD_SEP {
+            self.swap_lines(
+                a * BOARD_SEP + offset,
+                b * BOARD_SEP + offset,
+                true,
+            );
+        }
+    }
+
+    /// Swaps stacks `a` and `b`, keeping the order of the columns in each
+    ///
+    /// # Panics
+    ///
+    /// Panics if either stack index is greater than 2
+    pub fn swap_stacks(&mut self, a: usize, b: usize) {
+        assert!(a < BOARD_SEP && b < BOARD_SEP, "Stack index out of range");
+
+        for offset in 0..BOARD_SEP {
+            self.swap_lines(
+                a * BOARD_SEP + offset,
+                b * BOARD_SEP + offset,
+                false,
+            );
+        }
+    }
+
+    /// Swaps rows `a` and `b`, which must be in the same band
+    ///
+    /// # Panics
+    ///
+    /// Panics if a row is off the board or the rows are in different bands
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        assert!(a < BOARD_LEN && b < BOARD_LEN, "Row index out of range");
+        assert!(a / BOARD_SEP == b / BOARD_SEP, "Rows are in different bands");
+
+        self.swap_lines(a, b, true);
+    }
+
+    /// Swaps columns `a` and `b`, which must be in the same stack
+    ///
+    /// # Panics
+    ///
+    /// Panics if a column is off the board or the columns are in different
+    /// stacks
+    pub fn swap_cols(&mut self, a: usize, b: usize) {
+        assert!(a < BOARD_LEN && b < BOARD_LEN, "Column index out of range");
+        assert!(
+            a / BOARD_SEP == b / BOARD_SEP,
+            "Columns are in different stacks"
+        );
+
+        self.swap_lines(a, b, false);
+    }
+
+    /// Swaps two rows, or two columns if `rows` is false. Parity constraints,
+    /// extra groups and regions move with their cells
+    fn swap_lines(&mut self, a: usize, b: usize, rows: bool) {
+        let moved = |idx: usize| {
+            let (row, col) = Self::idx_to_coord(idx);
+            let line = if rows { row } else { col };
+
+            let line = match line {
+                line if line == a => b,
+                line if line == b => a,
+                line => line,
+            };
+
+            Self::coord_to_idx(if rows { (line, col) } else { (row, line) })
+        };
+
+        let old = *self;
+        let mut regions = old.layout.regions;
+        let mut extra_peers = old.layout.extra_peers;
+        let move_cells = |mask: u128| {
+            Self::cells_in(mask).fold(0, |mask, cell| mask | 1 << moved(cell))
+        };
+
+        for idx in 0..BOARD_SIZE {
+            let to = moved(idx);
+
+            self.grid[to] = old.grid[idx];
+            regions[to] = old.layout.regions[idx];
+            extra_peers[to] = move_cells(old.layout.extra_peers[idx]);
+        }
+
+        self.odd_cells = move_cells(old.odd_cells);
+        self.even_cells = move_cells(old.even_cells);
+        self.layout = Layout::new(&extra_peers, &regions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Symmetry;
+    use crate::Sudoku;
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    const HARD_SOLUTION: &str = "126437958895621473374985126457193862983246517612578394269314785548769231731852649";
+
+    const PERMUTATION: [u8; 9] = [3, 7, 1, 9, 2, 8, 5, 4, 6];
+
+    // Arto Inkala's puzzle, whose clues have no symmetry
+    const INKALA: &str = "800000000003600000070090200050007000000045700000100030001000068008500010090000400";
+
+    #[test]
+    fn clue_symmetry_finds_a_half_turn() {
+        let hard: Sudoku = HARD.parse().unwrap();
+        assert_eq!(hard.clue_symmetry(), [Symmetry::Rotational180]);
+
+        let inkala: Sudoku = INKALA.parse().unwrap();
+        assert!(inkala.clue_symmetry().is_empty());
+
+        // With no clues at all, every symmetry holds
+        assert_eq!(Sudoku::empty().clue_symmetry(), Symmetry::ALL);
+    }
+
+    #[test]
+    fn permutation_and_its_inverse_cancel_out() {
+        let mut inverse = [0; 9];
+        for (from, &to) in (1..).zip(&PERMUTATION) {
+            inverse[usize::from(to) - 1] = from;
+        }
+
+        let mut board: Sudoku = HARD.parse().unwrap();
+        board.initialize_superpositions();
+        board.propagate_all();
+        let original = board;
+
+        board.apply_permutation(PERMUTATION);
+        assert!(!board.values_eq(&original));
+        assert!(board.validate().is_ok());
+
+        board.apply_permutation(inverse);
+        assert_eq!(board.grid, original.grid);
+    }
+
+    #[test]
+    fn relabeled_puzzle_solves_to_the_relabeled_solution() {
+        let mut puzzle: Sudoku = HARD.parse().unwrap();
+        puzzle.apply_permutation(PERMUTATION);
+
+        let mut solution: Sudoku = HARD_SOLUTION.parse().unwrap();
+        solution.apply_permutation(PERMUTATION);
+        assert!(solution.is_complete_and_correct());
+
+        assert!(puzzle.solve_into().unwrap().values_eq(&solution));
+    }
+
+    #[test]
+    #[should_panic(expected = "Permutation repeats a value")]
+    fn permutation_repeating_a_value_panics() {
+        Sudoku::empty().apply_permutation([1, 2, 3, 4, 5, 6, 7, 8, 8]);
+    }
+
+    #[test]
+    fn swapping_twice_is_the_identity() {
+        let solved: Sudoku = HARD_SOLUTION.parse().unwrap();
+
+        let swaps: [fn(&mut Sudoku); 4] = [
+            |board| board.swap_bands(0, 2),
+            |board| board.swap_stacks(1, 2),
+            |board| board.swap_rows(3, 5),
+            |board| board.swap_cols(6, 7),
+        ];
+
+        for swap in swaps {
+            let mut board = solved;
+
+            swap(&mut board);
+            assert_ne!(board.grid, solved.grid);
+            assert!(board.is_complete_and_correct());
+
+            swap(&mut board);
+            assert_eq!(board.grid, solved.grid);
+        }
+    }
+
+    #[test]
+    fn swapped_bands_and_stacks_move_whole_units() {
+        let solved: Sudoku = HARD_SOLUTION.parse().unwrap();
+
+        let mut board = solved;
+        board.swap_bands(0, 1);
+        assert_eq!(board.bands()[0], solved.bands()[1]);
+        assert_eq!(board.bands()[1], solved.bands()[0]);
+        assert_eq!(board.bands()[2], solved.bands()[2]);
+
+        let mut board = solved;
+        board.swap_stacks(0, 2);
+        assert_eq!(board.stacks()[0], solved.stacks()[2]);
+        assert_eq!(board.stacks()[2], solved.stacks()[0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Band index out of range")]
+    fn swapping_a_band_off_the_board_panics() {
+        Sudoku::empty().swap_bands(0, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stack index out of range")]
+    fn swapping_a_stack_off_the_board_panics() {
+        Sudoku::empty().swap_stacks(3, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Rows are in different bands")]
+    fn swapping_rows_across_bands_panics() {
+        Sudoku::empty().swap_rows(2, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Column index out of range")]
+    fn swapping_a_column_off_the_board_panics() {
+        Sudoku::empty().swap_cols(8, 9);
+    }
+}
@@ -0,0 +1,259 @@
+mod locked_candidates;
+mod remote_pairs;
+mod subsets;
+mod unique_rectangle;
+mod wings;
+
+use std::fmt;
+
+use crate::{BOARD_SIZE, Cell, Search, SolverConfig, Strategy, Sudoku};
+
+/// The ways the solver can make progress on a board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Technique {
+    /// A cell with a single remaining candidate
+    NakedSingle,
+
+    /// The only cell in a row, column or subsection that can hold a value
+    HiddenSingle,
+
+    /// A value confined to one line of a subsection, removed from the rest of
+    /// the line
+    Pointing,
+
+    /// A value confined to one subsection within a line, removed from the
+    /// rest of the subsection
+    Claiming,
+
+    /// Three cells in a unit that hold only three values between them
+    NakedTriple,
+
+    /// Three values in a unit that can only go in the same three cells
+    HiddenTriple,
+
+    /// Four cells in a unit that hold only four values between them
+    NakedQuad,
+
+    /// Four values in a unit that can only go in the same four cells
+    HiddenQuad,
+
+    /// A candidate removed to avoid a deadly rectangle
+    UniqueRectangle,
+
+    /// A candidate removed from a cell seeing both ends of a chain of
+    /// identical bivalue cells
+    RemotePair,
+
+    /// A candidate removed from a cell seeing both pincers of an XY-wing
+    XyWing,
+
+    /// A candidate removed from a cell seeing both ends of a W-wing
+    WWing,
+
+    /// A value tried during backtracking
+    Guess,
+}
+
+impl Technique {
+    pub const ALL: [Self; 13] = [
+        Self::NakedSingle,
+        Self::HiddenSingle,
+        Self::Pointing,
+        Self::Claiming,
+        Self::NakedTriple,
+        Self::HiddenTriple,
+        Self::NakedQuad,
+        Self::HiddenQuad,
+        Self::UniqueRectangle,
+        Self::RemotePair,
+        Self::XyWing,
+        Self::WWing,
+        Self::Guess,
+    ];
+}
+
+impl fmt::Display for Technique {
+    /// The technique's name as a solving guide would write it, e.g.
+    /// `hidden single` or `XY-wing`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::NakedSingle => "naked single",
+            Self::HiddenSingle => "hidden single",
+            Self::Pointing => "pointing",
+            Self::Claiming => "claiming",
+            Self::NakedTriple => "naked triple",
+            Self::HiddenTriple => "hidden triple",
+            Self::NakedQuad => "naked quad",
+            Self::HiddenQuad => "hidden quad",
+            Self::UniqueRectangle => "unique rectangle",
+            Self::RemotePair => "remote pair",
+            Self::XyWing => "XY-wing",
+            Self::WWing => "W-wing",
+            Self::Guess => "guess",
+        };
+
+        f.write_str(name)
+    }
+}
+
+impl Technique {
+    /// Candidate-elimination techniques, cheapest first
+    const ELIMINATIONS: [Self; 10] = [
+        Self::Pointing,
+        Self::Claiming,
+        Self::NakedTriple,
+        Self::HiddenTriple,
+        Self::NakedQuad,
+        Self::HiddenQuad,
+        Self::UniqueRectangle,
+        Self::RemotePair,
+        Self::XyWing,
+        Self::WWing,
+    ];
+
+    const fn is_enabled(self, config: &SolverConfig) -> bool {
+        match self {
+            Self::NakedQuad | Self::HiddenQuad => config.quads,
+            Self::UniqueRectangle => config.unique_rectangles,
+            Self::RemotePair => config.remote_pairs,
+            Self::XyWing => config.xy_wings,
+            Self::WWing => config.w_wings,
+            _ => true,
+        }
+    }
+}
+
+impl Sudoku {
+    /// Applies a single elimination technique across the whole board,
+    /// returning the number of candidates removed
+    fn apply_technique(&mut self, technique: Technique) -> usize {
+        match technique {
+            Technique::Pointing => self.solve_pointing(),
+            Technique::Claiming => self.solve_claiming(),
+            Technique::NakedTriple => self.solve_naked_triples(),
+            Technique::HiddenTriple => self.solve_hidden_triples(),
+            Technique::NakedQuad => self.solve_naked_quads(),
+            Technique::HiddenQuad => self.solve_hidden_quads(),
+            Technique::UniqueRectangle => self.solve_unique_rectangles(),
+            Technique::RemotePair => self.solve_remote_pairs(),
+            Technique::XyWing => self.solve_xy_wings(),
+            Technique::WWing => self.solve_w_wings(),
+            Technique::NakedSingle
+            | Technique::HiddenSingle
+            | Technique::Guess => 0,
+        }
+    }
+
+    /// Runs the candidate-elimination techniques enabled in the search's
+    /// config, stopping at the first one that makes progress. Returns whether
+    /// any candidate was removed
+    pub(crate) fn apply_eliminations(&mut self, ctx: &mut Search<'_>) -> bool {
+        for technique in Technique::ELIMINATIONS {
+            if !technique.is_enabled(ctx.config) {
+                continue;
+            }
+
+            // Only copy the board when there are steps to diff it against
+            let before = ctx.steps.is_some().then_some(self.grid);
+            let eliminated = self.apply_technique(technique);
+            ctx.record_many(technique, eliminated);
+
+            for (idx, (before, after)) in
+                before.into_iter().flatten().zip(self.grid).enumerate()
+            {
+                if before != after {
+                    ctx.record_step(technique, idx, before, after);
+                }
+            }
+
+            if eliminated > 0 {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+impl Strategy for Technique {
+    /// Applies the technique once across the whole board. Singles are placed
+    /// and removed from their peers' candidates; a [`Technique::Guess`] never
+    /// makes progress
+    fn apply(&self, sudoku: &mut Sudoku) -> bool {
+        let mut changed = false;
+
+        match *self {
+            Self::NakedSingle => {
+                for idx in 0..BOARD_SIZE {
+                    if let Some(value) = sudoku.grid[idx].collapse() {
+                        sudoku.grid[idx] = Cell::Collapsed(value);
+                        sudoku.propagate(idx);
+                        changed = true;
+                    }
+                }
+            }
+            Self::HiddenSingle => {
+                // Hidden singles are only sound once every filled cell has
+                // been removed from its peers' candidates
+                sudoku.propagate_all();
+
+                for idx in 0..BOARD_SIZE {
+                    if sudoku.solve_pure_negative(idx) {
+                        sudoku.propagate(idx);
+                        changed = true;
+                    }
+                }
+            }
+            Self::Guess => {}
+            technique => changed = sudoku.apply_technique(technique) > 0,
+        }
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Technique::{self, *};
+    use crate::{Strategy, Sudoku};
+
+    /// Singles, locked candidates and every subset size, which the rarer
+    /// techniques are tested on top of
+    pub(super) const WITH_QUADS: [Technique; 8] = [
+        NakedSingle,
+        HiddenSingle,
+        Pointing,
+        Claiming,
+        NakedTriple,
+        HiddenTriple,
+        NakedQuad,
+        HiddenQuad,
+    ];
+
+    /// Applies `techniques` to the puzzle until none of them makes progress
+    pub(super) fn solve_using(
+        puzzle: &str,
+        techniques: &[Technique],
+    ) -> Sudoku {
+        let mut board: Sudoku = puzzle.parse().unwrap();
+        board.initialize_superpositions();
+        board.propagate_all();
+
+        while techniques.iter().any(|technique| technique.apply(&mut board)) {}
+
+        board
+    }
+
+    /// Whether `technique` is what lets the puzzle be solved after
+    /// `techniques` stall
+    pub(super) fn unblocks(
+        puzzle: &str,
+        techniques: &[Technique],
+        technique: Technique,
+    ) -> bool {
+        let with = [techniques, &[technique]].concat();
+
+        !solve_using(puzzle, techniques).is_complete_and_correct()
+            && solve_using(puzzle, &with).is_complete_and_correct()
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{BOARD_LEN, Cell, Sudoku, UnitKind};
+
+impl Sudoku {
+    /// Applies pointing: if every candidate for a value within a subsection
+    /// lies in the same row or column, the value must go in that part of the
+    /// line, so it can be removed from the rest of the line. Returns the
+    /// number of candidates eliminated
+    pub fn solve_pointing(&mut self) -> usize {
+        let mut eliminated = 0;
+
+        for index in 0..BOARD_LEN {
+            let cells = self.unit_cells(UnitKind::Box, index);
+
+            for line in [UnitKind::Row, UnitKind::Column] {
+                eliminated += self.eliminate_locked(cells, line);
+            }
+        }
+
+        eliminated
+    }
+
+    /// Applies claiming: if every candidate for a value within a row or
+    /// column lies in the same subsection, the value must go in that part of
+    /// the subsection, so it can be removed from the rest of the subsection.
+    /// Returns the number of candidates eliminated
+    pub fn solve_claiming(&mut self) -> usize {
+        let mut eliminated = 0;
+
+        for line in [UnitKind::Row, UnitKind::Column] {
+            for index in 0..BOARD_LEN {
+                let cells = self.unit_cells(line, index);
+                eliminated += self.eliminate_locked(cells, UnitKind::Box);
+            }
+        }
+
+        eliminated
+    }
+
+    /// For each value whose candidates in `unit` all lie within a single unit
+    /// of kind `other`, removes the value from the cells of that unit outside
+    /// `unit`
+    fn eliminate_locked(
+        &mut self,
+        unit: [usize; BOARD_LEN],
+        other: UnitKind,
+    ) -> usize {
+        let mut eliminated = 0;
+
+        for value in 1..=BOARD_LEN {
+            let value = u8::try_from(value).expect("Value out of range");
+            let bit = 1 << (value - 1);
+
+            let mut positions = unit.iter().copied().filter(|&idx| {
+                matches!(self.grid[idx], Cell::Superposition(_))
+                    && self.grid[idx].candidate_mask() & bit != 0
+            });
+
+            let Some(first) = positions.next() else {
+                continue;
+            };
+
+            let target = self.unit_of(other, first);
+
+            if !positions.all(|idx| self.unit_of(other, idx) == target) {
+                continue;
+            }
+
+            for idx in self.unit_cells(other, target) {
+                if !unit.contains(&idx) && self.eliminate(idx, value) {
+                    eliminated += 1;
+                }
+            }
+        }
+
+        eliminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Technique::*, technique::tests::unblocks};
+
+    #[test]
+    fn claiming_unblocks_the_solve() {
+        let puzzle = ".853....9.9..5..784...........9.61.3..7..4..5.6.......834....2......5.1.........7";
+
+        assert!(unblocks(
+            puzzle,
+            &[NakedSingle, HiddenSingle, Pointing],
+            Claiming
+        ));
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{BOARD_SIZE, Cell, Sudoku};
+
+impl Sudoku {
+    /// Applies remote pairs: a chain of cells that each hold only the same two
+    /// candidates, where each cell sees the next, must alternate between the
+    /// two values. Any other cell that sees two chain cells an odd number of
+    /// links apart sees both values, so neither can go there. Chains shorter
+    /// than four cells are left to the subset techniques. Returns the number
+    /// of candidates eliminated
+    pub fn solve_remote_pairs(&mut self) -> usize {
+        let mut eliminated = 0;
+        let mut visited = [false; BOARD_SIZE];
+
+        for start in 0..BOARD_SIZE {
+            let pair = self.grid[start].candidate_mask();
+
+            if visited[start]
+                || !matches!(self.grid[start], Cell::Superposition(_))
+                || pair.count_ones() != 2
+            {
+                continue;
+            }
+
+            let Some((chain, colours)) = self.remote_pair_chain(start, pair)
+            else {
+                continue;
+            };
+
+            for &idx in &chain {
+                visited[idx] = true;
+            }
+
+            if chain.len() < 4 {
+                continue;
+            }
+
+            for target in 0..BOARD_SIZE {
+                if colours[target].is_some()
+                    || !matches!(self.grid[target], Cell::Superposition(_))
+                    || self.grid[target].candidate_mask() & pair == 0
+                {
+                    continue;
+                }
+
+                let seen: Vec<usize> = self
+                    .peers(target)
+                    .filter(|&peer| colours[peer].is_some())
+                    .collect();
+
+                let sees_opposite_ends = seen.iter().any(|&a| {
+                    seen.iter()
+                        .any(|&b| colours[a] != colours[b] && !self.sees(a, b))
+                });
+
+                if sees_opposite_ends {
+                    eliminated += self.eliminate_mask(target, pair);
+                }
+            }
+        }
+
+        eliminated
+    }
+
+    /// Finds every cell linked to `start` through cells holding only the
+    /// candidates in `pair`, colouring them alternately. Returns `None` if
+    /// the links contain an odd cycle, as the colouring is then meaningless
+    fn remote_pair_chain(
+        &self,
+        start: usize,
+        pair: u16,
+    ) -> Option<(Vec<usize>, [Option<bool>; BOARD_SIZE])> {
+        let mut colours = [None; BOARD_SIZE];
+        colours[start] = Some(false);
+
+        let mut chain = vec![start];
+        let mut next = 0;
+
+        while let Some(&idx) = chain.get(next) {
+            next += 1;
+
+            for peer in self.peers(idx) {
+                if !matches!(self.grid[peer], Cell::Superposition(_))
+                    || self.grid[peer].candidate_mask() != pair
+                {
+                    continue;
+                }
+
+                match colours[peer] {
+                    None => {
+                        colours[peer] = colours[idx].map(|colour| !colour);
+                        chain.push(peer);
+                    }
+                    Some(colour) if Some(colour) == colours[idx] => {
+                        return None;
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        Some((chain, colours))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Technique,
+        technique::tests::{WITH_QUADS, unblocks},
+    };
+
+    #[test]
+    fn remote_pair_unblocks_the_solve() {
+        let puzzle = "...318.7.....9....5....238.3.54......61...2.5..............7.1.9.4.2.8..2......5.";
+
+        assert!(unblocks(puzzle, &WITH_QUADS, Technique::RemotePair));
+    }
+}
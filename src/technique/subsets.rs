@@ -0,0 +1,280 @@
+use crate::{BOARD_LEN, Cell, Sudoku};
+
+const TRIPLES: [u16; 84] = combinations(3);
+const QUADS: [u16; 126] = combinations(4);
+
+/// Every `size`-element subset of a unit's nine cells or values, as bitmasks
+/// in increasing order. `N` must be the number of such subsets
+const fn combinations<const N: usize>(size: u32) -> [u16; N] {
+    let mut subsets = [0; N];
+    let mut len = 0;
+    let mut mask: u16 = 0;
+
+    while mask < 1 << BOARD_LEN {
+        if mask.count_ones() == size {
+            subsets[len] = mask;
+            len += 1;
+        }
+        mask += 1;
+    }
+
+    assert!(len == N, "Wrong number of subsets");
+    subsets
+}
+
+impl Sudoku {
+    /// Applies naked triples: if three cells in a unit hold only three values
+    /// between them, those values can be removed from every other cell in the
+    /// unit. Returns the number of candidates eliminated
+    pub fn solve_naked_triples(&mut self) -> usize {
+        self.solve_naked_subsets(3)
+    }
+
+    /// Applies hidden triples: if three values can only go in the same three
+    /// cells of a unit, every other candidate can be removed from those cells.
+    /// Returns the number of candidates eliminated
+    pub fn solve_hidden_triples(&mut self) -> usize {
+        self.solve_hidden_subsets(3)
+    }
+
+    /// Applies naked quads, the four-cell version of
+    /// [`Sudoku::solve_naked_triples`]. Returns the number of candidates
+    /// eliminated
+    pub fn solve_naked_quads(&mut self) -> usize {
+        self.solve_naked_subsets(4)
+    }
+
+    /// Applies hidden quads, the four-value version of
+    /// [`Sudoku::solve_hidden_triples`]. Returns the number of candidates
+    /// eliminated
+    pub fn solve_hidden_quads(&mut self) -> usize {
+        self.solve_hidden_subsets(4)
+    }
+
+    /// Every way of choosing `size` of the first `len` cells or values of a
+    /// unit, as bitmasks in increasing order
+    fn subsets_of(len: usize, size: u32) -> impl Iterator<Item = u16> {
+        let subsets: &[u16] = match size {
+            3 => &TRIPLES,
+            4 => &QUADS,
+            _ => unreachable!("Only triples and quads are searched for"),
+        };
+
+        subsets.iter().copied().take_while(move |&subset| subset < 1 << len)
+    }
+
+    pub(crate) fn solve_naked_subsets(&mut self, size: u32) -> usize {
+        let mut eliminated = 0;
+
+        for unit in self.units() {
+            // The open cells of the unit and their candidates, kept up to date
+            // as values are eliminated
+            let mut open = [(0, 0); BOARD_LEN];
+            let mut len = 0;
+
+            for idx in unit {
+                if matches!(self.grid[idx], Cell::Superposition(_)) {
+                    open[len] = (idx, self.grid[idx].candidate_mask());
+                    len += 1;
+                }
+            }
+
+            let open = &mut open[..len];
+
+            for chosen in Self::subsets_of(len, size) {
+                let in_subset = |i: usize| chosen & (1 << i) != 0;
+
+                let values = open
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| in_subset(i))
+                    .fold(0, |mask, (_, &(_, values))| mask | values);
+
+                if values.count_ones() != size {
+                    continue;
+                }
+
+                for (_, (idx, mask)) in
+                    open.iter_mut().enumerate().filter(|(i, _)| !in_subset(*i))
+                {
+                    eliminated += self.eliminate_mask(*idx, values);
+                    *mask &= !values;
+                }
+            }
+        }
+
+        eliminated
+    }
+
+    pub(crate) fn solve_hidden_subsets(&mut self, size: u32) -> usize {
+        let mut eliminated = 0;
+
+        for unit in self.units() {
+            // The positions within the unit of the open cells that can hold
+            // each value, kept up to date as values are eliminated
+            let mut positions = [0u16; BOARD_LEN];
+
+            for (i, &idx) in unit.iter().enumerate() {
+                if matches!(self.grid[idx], Cell::Superposition(_)) {
+                    let mask = self.grid[idx].candidate_mask();
+
+                    for (value, cells) in positions.iter_mut().enumerate() {
+                        if mask & (1 << value) != 0 {
+                            *cells |= 1 << i;
+                        }
+                    }
+                }
+            }
+
+            // Only values still to be placed can form a subset. Each one
+            // needs somewhere to go, otherwise the board is already
+            // contradictory and the next pass will notice
+            let placed = unit
+                .iter()
+                .filter_map(|&idx| self.grid[idx].value())
+                .fold(0, |mask, value| mask | 1 << (value - 1));
+
+            let mut open = [0; BOARD_LEN];
+            let mut len = 0;
+
+            for (value, &cells) in positions.iter().enumerate() {
+                if placed & (1 << value) == 0 && cells != 0 {
+                    open[len] = value;
+                    len += 1;
+                }
+            }
+
+            for chosen in Self::subsets_of(len, size) {
+                let mut values = 0;
+                let mut cells = 0;
+
+                for (_, &value) in open
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| chosen & (1 << i) != 0)
+                {
+                    values |= 1 << value;
+                    cells |= positions[value];
+                }
+
+                // Eliminating an earlier subset can't empty a value without
+                // making the board contradictory, which is left to the next
+                // pass too
+                if cells.count_ones() != size
+                    || open[..len].iter().any(|&value| {
+                        values & (1 << value) != 0 && positions[value] == 0
+                    })
+                {
+                    continue;
+                }
+
+                for (i, &idx) in unit.iter().enumerate() {
+                    if cells & (1 << i) != 0 {
+                        eliminated += self.eliminate_mask(idx, !values);
+                    }
+                }
+
+                for (value, others) in positions.iter_mut().enumerate() {
+                    if values & (1 << value) == 0 {
+                        *others &= !cells;
+                    }
+                }
+            }
+        }
+
+        eliminated
+    }
+
+    /// Removes every value in `mask` from the candidates of the cell at `idx`,
+    /// returning how many were removed
+    pub(crate) fn eliminate_mask(&mut self, idx: usize, mask: u16) -> usize {
+        (1..=BOARD_LEN)
+            .filter(|value| mask & (1 << (value - 1)) != 0)
+            .filter(|&value| {
+                self.eliminate(
+                    idx,
+                    u8::try_from(value).expect("Value out of range"),
+                )
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Sudoku,
+        Technique::{self, *},
+        technique::tests::{solve_using, unblocks},
+    };
+
+    const LOCKED: [Technique; 4] =
+        [NakedSingle, HiddenSingle, Pointing, Claiming];
+
+    const TRIPLES: [Technique; 6] = [
+        NakedSingle,
+        HiddenSingle,
+        Pointing,
+        Claiming,
+        NakedTriple,
+        HiddenTriple,
+    ];
+
+    // Stalls after locked candidates until a triple is found, which either
+    // kind of triple can do
+    const TRIPLE: &str = "....9...4..93..2.......6.83.42...63....8......5.....2.....18.....4.53..7..56..1..";
+
+    const NAKED_QUAD: &str = "69.1...2...4.......2.7...3..7..8...........63..39.2.81......3.29...47..8...3...94";
+
+    const HIDDEN_QUAD: &str = "..8.....4.2.93.8...4.7...39...8...25..6..2..........8...1.2.....835......5.3..7..";
+
+    #[test]
+    fn naked_triple_unblocks_the_solve() {
+        assert!(unblocks(TRIPLE, &LOCKED, NakedTriple));
+    }
+
+    #[test]
+    fn hidden_triple_unblocks_the_solve() {
+        assert!(unblocks(TRIPLE, &LOCKED, HiddenTriple));
+    }
+
+    #[test]
+    fn naked_quad_unblocks_the_solve() {
+        assert!(unblocks(NAKED_QUAD, &TRIPLES, NakedQuad));
+    }
+
+    #[test]
+    fn hidden_quad_unblocks_the_solve() {
+        assert!(unblocks(HIDDEN_QUAD, &TRIPLES, HiddenQuad));
+    }
+
+    #[test]
+    fn quads_find_nothing_on_an_empty_board() {
+        let mut board = Sudoku::empty();
+        board.initialize_superpositions();
+
+        assert_eq!(board.solve_naked_quads(), 0);
+        assert_eq!(board.solve_hidden_quads(), 0);
+    }
+
+    #[test]
+    fn quads_keep_every_solution_value() {
+        for puzzle in [TRIPLE, NAKED_QUAD, HIDDEN_QUAD] {
+            let mut solution: Sudoku = puzzle.parse().unwrap();
+            solution.initialize_superpositions();
+            solution.solve().unwrap();
+
+            let mut board = solve_using(puzzle, &TRIPLES);
+            board.solve_naked_quads();
+            board.solve_hidden_quads();
+
+            for (cell, solved) in board.grid.iter().zip(solution.grid) {
+                let value = solved.value().unwrap();
+                assert!(cell.value().map_or_else(
+                    || cell.candidate_mask() & 1 << (value - 1) != 0,
+                    |n| n == value
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{BOARD_LEN, Cell, Sudoku};
+
+impl Sudoku {
+    /// Applies type 1 unique rectangles: if three corners of a rectangle
+    /// spanning two rows, two columns and two regions hold only the same
+    /// two candidates, the fourth corner can't take either of them, or the
+    /// two values could be swapped to give a second solution. Only valid for
+    /// puzzles with a unique solution. Returns the number of candidates
+    /// eliminated
+    pub fn solve_unique_rectangles(&mut self) -> usize {
+        let mut eliminated = 0;
+
+        for r1 in 0..BOARD_LEN {
+            for r2 in r1 + 1..BOARD_LEN {
+                for c1 in 0..BOARD_LEN {
+                    for c2 in c1 + 1..BOARD_LEN {
+                        let corners = [
+                            Self::coord_to_idx((r1, c1)),
+                            Self::coord_to_idx((r1, c2)),
+                            Self::coord_to_idx((r2, c1)),
+                            Self::coord_to_idx((r2, c2)),
+                        ];
+
+                        let [a, b, c, d] =
+                            corners.map(|idx| self.region_of(idx));
+
+                        // The corners must cover exactly two regions, split
+                        // between the rows or between the columns
+                        let rows_split = a == b && c == d && a != c;
+                        let cols_split = a == c && b == d && a != b;
+
+                        if rows_split || cols_split {
+                            eliminated += self.eliminate_rectangle(corners);
+                        }
+                    }
+                }
+            }
+        }
+
+        eliminated
+    }
+
+    fn eliminate_rectangle(&mut self, corners: [usize; 4]) -> usize {
+        if !corners
+            .iter()
+            .all(|&idx| matches!(self.grid[idx], Cell::Superposition(_)))
+        {
+            return 0;
+        }
+
+        let masks = corners.map(|idx| self.grid[idx].candidate_mask());
+
+        for (roof, &mask) in masks.iter().enumerate() {
+            if mask.count_ones() == 2 {
+                continue;
+            }
+
+            let mut others = masks
+                .iter()
+                .enumerate()
+                .filter(|&(idx, _)| idx != roof)
+                .map(|(_, &mask)| mask);
+
+            let pair = others.next().expect("A rectangle has four corners");
+
+            if pair.count_ones() != 2
+                || others.any(|other| other != pair)
+                || mask & pair != pair
+            {
+                continue;
+            }
+
+            return self.eliminate_mask(corners[roof], pair);
+        }
+
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        SolveError, SolverConfig, Sudoku, Technique,
+        technique::tests::{WITH_QUADS, unblocks},
+    };
+
+    const PUZZLE: &str = "..2.36....1..7........2..41.7.4.3....5....3.4....9..175...8.....41...9.69....7...";
+
+    #[test]
+    fn unique_rectangle_unblocks_the_solve() {
+        assert!(unblocks(PUZZLE, &WITH_QUADS, Technique::UniqueRectangle));
+    }
+
+    #[test]
+    fn unique_rectangles_are_opt_in() {
+        let solve = |unique_rectangles| {
+            let config = SolverConfig {
+                backtracking: false,
+                quads: true,
+                unique_rectangles,
+                ..SolverConfig::new()
+            };
+
+            let mut board: Sudoku = PUZZLE.parse().unwrap();
+            board.initialize_superpositions();
+            board.solve_with_config(&config)
+        };
+
+        assert_eq!(solve(false), Err(SolveError::RequiresGuessing));
+        assert_eq!(solve(true), Ok(()));
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{BOARD_SIZE, Cell, Sudoku};
+
+impl Sudoku {
+    /// The cells with a superposition of exactly two candidates
+    fn bivalue_cells(&self) -> Vec<usize> {
+        (0..BOARD_SIZE)
+            .filter(|&idx| {
+                matches!(self.grid[idx], Cell::Superposition(_))
+                    && self.grid[idx].candidate_mask().count_ones() == 2
+            })
+            .collect()
+    }
+
+    /// Removes the candidates in `mask` from every cell other than `a` and
+    /// `b` that sees both of them, returning the number eliminated
+    fn eliminate_seen_by_both(
+        &mut self,
+        a: usize,
+        b: usize,
+        mask: u16,
+    ) -> usize {
+        self.common_peers(a, b)
+            .into_iter()
+            .map(|target| self.eliminate_mask(target, mask))
+            .sum()
+    }
+
+    /// Applies XY-wings: a pivot cell holding only `x` and `y` that sees one
+    /// cell holding only `x` and `z` and another holding only `y` and `z`.
+    /// Whichever value the pivot takes, one of these pincers must be `z`, so
+    /// `z` can't go in any cell seeing both pincers. Returns the number of
+    /// candidates eliminated
+    pub fn solve_xy_wings(&mut self) -> usize {
+        let bivalues = self.bivalue_cells();
+        let mut eliminated = 0;
+
+        for &pivot in &bivalues {
+            let pivot_mask = self.grid[pivot].candidate_mask();
+
+            for &a in &bivalues {
+                for &b in &bivalues {
+                    let (a_mask, b_mask) = (
+                        self.grid[a].candidate_mask(),
+                        self.grid[b].candidate_mask(),
+                    );
+
+                    // Each pincer shares a different pivot value, and the
+                    // other value is the same for both
+                    let z = a_mask & b_mask;
+
+                    if a >= b
+                        || !self.sees(pivot, a)
+                        || !self.sees(pivot, b)
+                        || z.count_ones() != 1
+                        || z & pivot_mask != 0
+                        || (a_mask | b_mask) & !z != pivot_mask
+                    {
+                        continue;
+                    }
+
+                    eliminated += self.eliminate_seen_by_both(a, b, z);
+                }
+            }
+        }
+
+        eliminated
+    }
+
+    /// Applies W-wings: two cells that don't see each other, both holding
+    /// only `x` and `y`, joined by a unit where `x` can only go in two
+    /// places, one seeing each cell. One of those places must be `x`, so one
+    /// of the two cells must be `y`, and `y` can't go in any cell seeing both.
+    /// Returns the number of candidates eliminated
+    pub fn solve_w_wings(&mut self) -> usize {
+        let bivalues = self.bivalue_cells();
+        let mut eliminated = 0;
+
+        for &a in &bivalues {
+            for &b in &bivalues {
+                let pair = self.grid[a].candidate_mask();
+
+                if a >= b
+                    || self.grid[b].candidate_mask() != pair
+                    || self.sees(a, b)
+                {
+                    continue;
+                }
+
+                for x in [pair & pair.wrapping_neg(), pair & (pair - 1)] {
+                    if self.strong_link_between(a, b, x) {
+                        eliminated +=
+                            self.eliminate_seen_by_both(a, b, pair & !x);
+                    }
+                }
+            }
+        }
+
+        eliminated
+    }
+
+    /// Whether some unit has exactly two cells with candidate `value` (as a
+    /// one-bit mask), neither of them `a` or `b`, with one seeing `a` and the
+    /// other seeing `b`
+    fn strong_link_between(&self, a: usize, b: usize, value: u16) -> bool {
+        self.units().into_iter().any(|unit| {
+            let mut places = unit.into_iter().filter(|&idx| {
+                matches!(self.grid[idx], Cell::Superposition(_))
+                    && self.grid[idx].candidate_mask() & value != 0
+            });
+
+            let (Some(p), Some(q), None) =
+                (places.next(), places.next(), places.next())
+            else {
+                return false;
+            };
+
+            ![p, q].contains(&a)
+                && ![p, q].contains(&b)
+                && ((self.sees(p, a) && self.sees(q, b))
+                    || (self.sees(q, a) && self.sees(p, b)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Technique,
+        technique::tests::{WITH_QUADS, unblocks},
+    };
+
+    #[test]
+    fn xy_wing_unblocks_the_solve() {
+        for puzzle in [
+            "..........28..16.....4.8..9.8...53......43.....2.6.8...6......55...1..9..7......4",
+            "8....9.633.........251.8....5..4...7....27..6..48...2...37............3.....56.14",
+        ] {
+            assert!(unblocks(puzzle, &WITH_QUADS, Technique::XyWing));
+        }
+    }
+
+    #[test]
+    fn w_wing_unblocks_the_solve() {
+        let puzzle = "....37......9.1.8.....8...19....38...2......6.3.742..9.48...3........12.2....9.47";
+
+        assert!(unblocks(puzzle, &WITH_QUADS, Technique::WWing));
+    }
+}
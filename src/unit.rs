@@ -0,0 +1,408 @@
+use crate::{BOARD_LEN, Cell, Conflict, Sudoku};
+
+/// The three kinds of unit in which every value must appear exactly once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnitKind {
+    Row,
+    Column,
+    Box,
+}
+
+impl Sudoku {
+    /// Indices of the cells making up a unit in board order. Rows and
+    /// columns are numbered left to right and top to bottom, and boxes are
+    /// numbered by region, which for the standard layout is row-major order
+    pub(crate) fn unit_cells(
+        &self,
+        kind: UnitKind,
+        index: usize,
+    ) -> [usize; BOARD_LEN] {
+        match kind {
+            UnitKind::Row => std::array::from_fn(|i| index * BOARD_LEN + i),
+            UnitKind::Column => std::array::from_fn(|i| i * BOARD_LEN + index),
+            UnitKind::Box => self.layout.region_cells[index].map(usize::from),
+        }
+    }
+
+    /// The index of the unit of the given kind containing the cell at `idx`
+    pub(crate) const fn unit_of(&self, kind: UnitKind, idx: usize) -> usize {
+        match kind {
+            UnitKind::Row => Self::row_of(idx),
+            UnitKind::Column => Self::col_of(idx),
+            UnitKind::Box => self.region_of(idx),
+        }
+    }
+
+    /// The cells of every row, column and region
+    pub(crate) fn units(&self) -> [[usize; BOARD_LEN]; 3 * BOARD_LEN] {
+        std::array::from_fn(|i| {
+            let kind =
+                [UnitKind::Row, UnitKind::Column, UnitKind::Box][i / BOARD_LEN];
+
+            self.unit_cells(kind, i % BOARD_LEN)
+        })
+    }
+
+    /// Whether every value still has somewhere to go in every unit, counting
+    /// both filled cells and candidates. A board failing this has no solution
+    pub(crate) fn every_value_has_a_cell(&self) -> bool {
+        self.units().iter().all(|unit| {
+            unit.iter()
+                .fold(0, |mask, &idx| mask | self.grid[idx].candidate_mask())
+                == (1 << BOARD_LEN) - 1
+        })
+    }
+
+    /// Checks that the puzzle isn't broken in a way the solver would only
+    /// discover by searching. After removing every filled value from its
+    /// peers' candidates, each unit must have, for every set of its missing
+    /// values, at least as many blanks able to take one of them as there are
+    /// values in the set
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Conflict::Duplicate`] as [`Sudoku::validate`] would, or
+    /// [`Conflict::Unsatisfiable`] for the first row, then column, then
+    /// subsection that can't be completed
+    pub fn check_satisfiable(&self) -> Result<(), Conflict> {
+        self.validate()?;
+
+        let mut board = *self;
+        board.initialize_superpositions();
+        board.propagate_all();
+
+        let unsatisfiable = [UnitKind::Row, UnitKind::Column, UnitKind::Box]
+            .into_iter()
+            .flat_map(|kind| (0..BOARD_LEN).map(move |index| (kind, index)))
+            .find(|&(kind, index)| !board.unit_satisfiable(kind, index));
+
+        match unsatisfiable {
+            Some((kind, index)) => Err(Conflict::Unsatisfiable { kind, index }),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether every subset of a unit's missing values has enough blanks to
+    /// go in
+    pub(crate) fn unit_satisfiable(
+        &self,
+        kind: UnitKind,
+        index: usize,
+    ) -> bool {
+        let missing = self.remaining_in_unit(kind, index);
+
+        let blanks: Vec<u16> = self
+            .unit_cells(kind, index)
+            .iter()
+            .filter(|&&idx| self.grid[idx].value().is_none())
+            .map(|&idx| self.grid[idx].candidate_mask())
+            .collect();
+
+        (1..=missing).filter(|values| values & !missing == 0).all(|values| {
+            let cells =
+                blanks.iter().filter(|&&mask| mask & values != 0).count();
+
+            cells >= values.count_ones() as usize
+        })
+    }
+
+    /// The number of clues in each row, column and subsection, in that order.
+    /// Each array sums to [`Sudoku::clue_count`]
+    #[must_use]
+    pub fn count_givens_per_unit(
+        &self,
+    ) -> ([u8; BOARD_LEN], [u8; BOARD_LEN], [u8; BOARD_LEN]) {
+        let count = |kind| {
+            std::array::from_fn(|index| {
+                self.unit_cells(kind, index)
+                    .iter()
+                    .filter(|&&idx| matches!(self.grid[idx], Cell::Fixed(_)))
+                    .fold(0, |count, _| count + 1)
+            })
+        };
+
+        (count(UnitKind::Row), count(UnitKind::Column), count(UnitKind::Box))
+    }
+
+    /// How many clues hold each value, indexed by `value - 1`. The counts sum
+    /// to [`Sudoku::clue_count`]
+    #[must_use]
+    pub fn fixed_digit_histogram(&self) -> [u8; BOARD_LEN] {
+        let mut histogram = [0; BOARD_LEN];
+
+        for cell in &self.grid {
+            if let Cell::Fixed(n) = cell {
+                histogram[*n as usize - 1] += 1;
+            }
+        }
+
+        histogram
+    }
+
+    /// The values not yet placed in a unit as a bitmask, where bit `n - 1` is
+    /// set if `n` is missing
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` isn't a valid unit index
+    #[must_use]
+    pub fn remaining_in_unit(&self, kind: UnitKind, index: usize) -> u16 {
+        assert!(index < BOARD_LEN, "Unit index out of range");
+
+        self.unit_cells(kind, index)
+            .iter()
+            .filter_map(|&idx| self.grid[idx].value())
+            .fold((1 << BOARD_LEN) - 1, |mask, value| {
+                mask & !(1 << (value - 1))
+            })
+    }
+
+    /// Fills in as much of a single unit as naked and hidden singles allow.
+    /// Constraints are read from the whole board, but only cells inside the
+    /// unit are changed
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` isn't a valid unit index
+    pub fn solve_unit(&mut self, kind: UnitKind, index: usize) {
+        assert!(index < BOARD_LEN, "Unit index out of range");
+
+        let cells = self.unit_cells(kind, index);
+
+        let mut board = *self;
+        board.initialize_superpositions();
+
+        for idx in 0..board.grid.len() {
+            board.propagate(idx);
+        }
+
+        let mut placed = true;
+
+        while placed {
+            placed = false;
+
+            for &idx in &cells {
+                if let Some(value) = board.grid[idx].collapse() {
+                    board.grid[idx] = Cell::Collapsed(value);
+                } else if !board.solve_pure_negative(idx) {
+                    continue;
+                }
+
+                board.propagate(idx);
+                placed = true;
+            }
+        }
+
+        for idx in cells {
+            if let Cell::Collapsed(value) = board.grid[idx]
+                && self.grid[idx].value().is_none()
+            {
+                self.grid[idx] = Cell::Collapsed(value);
+            }
+        }
+    }
+
+    /// Fills in the last blank of any row, column or subsection that has only
+    /// one left, repeating until none remain, the way people tend to start a
+    /// puzzle. Units are checked rows first, then columns, then subsections.
+    /// Returns the placements as `(row, col, value)` in the order they were
+    /// made
+    pub fn solve_by_completion(&mut self) -> Vec<(usize, usize, u8)> {
+        let mut placed = Vec::new();
+
+        while let Some((idx, value)) = self.next_completion() {
+            self.grid[idx] = Cell::Collapsed(value);
+            self.propagate(idx);
+
+            let (row, col) = Self::idx_to_coord(idx);
+            placed.push((row, col, value));
+        }
+
+        placed
+    }
+
+    /// The first unit with exactly one blank whose missing value can go there
+    fn next_completion(&self) -> Option<(usize, u8)> {
+        [UnitKind::Row, UnitKind::Column, UnitKind::Box]
+            .into_iter()
+            .flat_map(|kind| (0..BOARD_LEN).map(move |index| (kind, index)))
+            .find_map(|(kind, index)| {
+                let mut blanks = self
+                    .unit_cells(kind, index)
+                    .into_iter()
+                    .filter(|&idx| self.grid[idx].value().is_none());
+
+                let (Some(idx), None) = (blanks.next(), blanks.next()) else {
+                    return None;
+                };
+
+                let missing = self.remaining_in_unit(kind, index);
+
+                (missing.is_power_of_two()
+                    && self.grid[idx].candidate_mask() & missing != 0)
+                    .then(|| {
+                        let value = missing.trailing_zeros() + 1;
+                        (idx, u8::try_from(value).expect("Value out of range"))
+                    })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnitKind;
+    use crate::{Cell, Conflict, SolveError, Sudoku};
+
+    const HARD: &str = "020000000000600003074080000000003002080040010600500000000010780500009000000000040";
+
+    const HARD_SOLUTION: &str = "126437958895621473374985126457193862983246517612578394269314785548769231731852649";
+
+    #[test]
+    fn a_value_shut_out_of_a_row_is_caught_before_searching() {
+        // Row 1 has its blanks in the second and third subsections, and a 5
+        // in each of those subsections keeps 5 out of the whole row
+        let mut board = format!("1234{}", ".".repeat(77))
+            .parse::<Sudoku>()
+            .unwrap()
+            .with_clue(1, 4, 5)
+            .with_clue(2, 7, 5);
+
+        assert!(board.validate().is_ok());
+
+        board.initialize_superpositions();
+
+        let mut propagated = board;
+        propagated.propagate_all();
+        assert!(!propagated.every_value_has_a_cell());
+
+        assert_eq!(board.solve(), Err(SolveError::NoSolution));
+    }
+
+    #[test]
+    fn a_unit_with_too_few_blanks_for_its_values_is_unsatisfiable() {
+        // Row 1 is missing 7, 8 and 9, but with 7 and 8 already in columns 8
+        // and 9, only one of its blanks can take either of them
+        let board = format!("123456{}", ".".repeat(75))
+            .parse::<Sudoku>()
+            .unwrap()
+            .with_clue(3, 7, 7)
+            .with_clue(4, 7, 8)
+            .with_clue(6, 8, 7)
+            .with_clue(7, 8, 8);
+
+        assert!(board.validate().is_ok());
+
+        // Every value still has a cell, so the simpler check misses this
+        let mut propagated = board;
+        propagated.initialize_superpositions();
+        propagated.propagate_all();
+        assert!(propagated.every_value_has_a_cell());
+
+        assert_eq!(
+            board.check_satisfiable(),
+            Err(Conflict::Unsatisfiable { kind: UnitKind::Row, index: 0 })
+        );
+    }
+
+    #[test]
+    fn solving_a_unit_fills_only_that_unit() {
+        // Row 5 is given in full apart from three cells, which the clues in
+        // their columns and subsections pin down
+        let mut row = HARD_SOLUTION[36..45].to_string();
+        for col in [0, 5, 6] {
+            row.replace_range(col..=col, "0");
+        }
+
+        let puzzle: Sudoku =
+            format!("{}{row}{}", &HARD[..36], &HARD[45..]).parse().unwrap();
+
+        let mut board = puzzle;
+        board.solve_unit(UnitKind::Row, 4);
+
+        for (idx, (before, after)) in
+            puzzle.grid.iter().zip(&board.grid).enumerate()
+        {
+            if idx / 9 == 4 && before.value().is_none() {
+                let expected = HARD_SOLUTION.as_bytes()[idx] - b'0';
+                assert_eq!(*after, Cell::Collapsed(expected));
+            } else {
+                assert_eq!(before, after);
+            }
+        }
+    }
+
+    #[test]
+    fn remaining_values_are_the_ones_not_placed() {
+        // 1, 2 and 3 start row 1, which puts all three in subsection 1 but
+        // only the 1 in column 1
+        let board = Sudoku::empty()
+            .with_clue(0, 0, 1)
+            .with_clue(0, 1, 2)
+            .with_clue(0, 2, 3);
+
+        assert_eq!(board.remaining_in_unit(UnitKind::Row, 0), 0b1_1111_1000);
+        assert_eq!(board.remaining_in_unit(UnitKind::Box, 0), 0b1_1111_1000);
+        assert_eq!(board.remaining_in_unit(UnitKind::Column, 0), 0b1_1111_1110);
+        assert_eq!(board.remaining_in_unit(UnitKind::Row, 1), 0b1_1111_1111);
+
+        let solved: Sudoku = HARD_SOLUTION.parse().unwrap();
+        assert_eq!(solved.remaining_in_unit(UnitKind::Column, 4), 0);
+    }
+
+    #[test]
+    fn completion_fills_the_last_blank_of_each_unit_in_turn() {
+        let mut board: Sudoku = HARD_SOLUTION.parse().unwrap();
+        for (row, col) in [(0, 0), (0, 1), (1, 0)] {
+            board.set(row, col, 0);
+        }
+        board.initialize_superpositions();
+
+        // Row 2's only blank comes first, which leaves column 1 and then row
+        // 1 with one blank each
+        assert_eq!(
+            board.solve_by_completion(),
+            [(1, 0, 8), (0, 0, 1), (0, 1, 2)]
+        );
+        assert!(board.is_complete_and_correct());
+
+        // No unit of the hard puzzle is close to finished
+        let mut puzzle: Sudoku = HARD.parse().unwrap();
+        puzzle.initialize_superpositions();
+        assert!(puzzle.solve_by_completion().is_empty());
+    }
+
+    #[test]
+    fn givens_per_unit_each_sum_to_the_clue_count() {
+        let puzzle: Sudoku = HARD.parse().unwrap();
+        let (rows, cols, boxes) = puzzle.count_givens_per_unit();
+
+        for counts in [rows, cols, boxes] {
+            let total: usize = counts.iter().map(|&n| usize::from(n)).sum();
+            assert_eq!(total, puzzle.clue_count());
+        }
+
+        assert_eq!(rows, [1, 2, 3, 2, 3, 2, 3, 2, 1]);
+
+        // Solver placements aren't clues
+        let solved = puzzle.solve_into().unwrap();
+        assert_eq!(solved.count_givens_per_unit(), (rows, cols, boxes));
+    }
+
+    #[test]
+    fn digit_histogram_counts_clues_only() {
+        let solved: Sudoku = HARD_SOLUTION.parse().unwrap();
+        assert_eq!(solved.fixed_digit_histogram(), [9; 9]);
+
+        let puzzle: Sudoku = HARD.parse().unwrap();
+        let histogram = puzzle.fixed_digit_histogram();
+
+        let total: usize = histogram.iter().map(|&n| usize::from(n)).sum();
+        assert_eq!(total, puzzle.clue_count());
+        assert_eq!(histogram, [2, 2, 2, 3, 2, 2, 2, 3, 1]);
+
+        let mut solution = puzzle;
+        solution.initialize_superpositions();
+        solution.solve().unwrap();
+        assert_eq!(solution.fixed_digit_histogram(), histogram);
+    }
+}
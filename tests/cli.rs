@@ -0,0 +1,87 @@
+use std::process::Command;
+
+use simple_sudoku_solver::{Difficulty, SolutionClass, Sudoku};
+
+fn generate(args: &[&str]) -> Vec<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_simple_sudoku_solver"))
+        .arg("generate")
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn generate_prints_the_requested_puzzles() {
+    let lines =
+        generate(&["--difficulty", "easy", "--count", "3", "--seed", "42"]);
+
+    assert_eq!(lines.len(), 3);
+
+    for line in &lines {
+        let puzzle: Sudoku = line.parse().unwrap();
+
+        assert!(puzzle.validate().is_ok());
+        assert_eq!(puzzle.solution_class(), SolutionClass::Unique);
+        assert_eq!(puzzle.rate(), Some(Difficulty::Easy));
+    }
+}
+
+#[test]
+fn generate_is_reproducible_from_its_seed() {
+    let args = ["--count", "2", "--seed", "7"];
+
+    assert_eq!(generate(&args), generate(&args));
+    assert_ne!(generate(&args), generate(&["--count", "2", "--seed", "8"]));
+}
+
+#[test]
+fn generate_rejects_unknown_options() {
+    let output = Command::new(env!("CARGO_BIN_EXE_simple_sudoku_solver"))
+        .args(["generate", "--colour", "red"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn rate_reports_the_hard_sample_as_unique_and_hard() {
+    let path = std::env::temp_dir()
+        .join(format!("sudoku-rate-{}.txt", std::process::id()));
+    std::fs::write(
+        &path,
+        "020000000000600003074080000000003002080040010600500000000010780500009000000000040\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_simple_sudoku_solver"))
+        .arg("rate")
+        .arg(&path)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.lines().any(|line| line == "Solutions: unique"));
+
+    let difficulty = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Difficulty: "))
+        .unwrap();
+    let difficulty = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
+        .into_iter()
+        .find(|d| format!("{d:?}") == difficulty)
+        .unwrap();
+
+    assert!(difficulty >= Difficulty::Hard);
+}